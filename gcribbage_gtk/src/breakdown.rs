@@ -0,0 +1,258 @@
+use crate::callouts;
+use crate::widgets::{CardBox, TableCard};
+use gcribbage_lib::deck::Card;
+use gcribbage_lib::scoring::HandScorings;
+use relm4::gtk::glib::{self, BoxedAnyObject};
+use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+use std::time::Duration;
+
+/// How long each score stays picked out before the count moves on to the
+/// next one.
+const STEP_TIME: Duration = Duration::from_millis(1200);
+
+/// A hand that has just been scored, ready to be counted up.
+#[derive(Debug)]
+pub struct HandCount {
+    /// Whose hand it is, such as "Your crib".
+    pub title: String,
+    pub hand: Vec<Card>,
+    pub up_card: Card,
+    pub scorings: Vec<HandScorings>,
+}
+
+/// A window that counts up a hand one score at a time, the way a player
+/// would count it aloud, picking out the cards that make each score.
+pub struct Breakdown {
+    hidden: bool,
+    title: String,
+    hand: Vec<Card>,
+    up_card: Option<Card>,
+    scorings: Vec<HandScorings>,
+    /// How many of the scorings have been counted so far.
+    counted: usize,
+    /// How many hands have been shown, so a step scheduled while counting
+    /// an old hand doesn't move a newer one along.
+    shown: u32,
+    /// The name of the deck the cards are drawn from.
+    theme: String,
+    four_colour: bool,
+}
+
+#[derive(Debug)]
+pub enum BreakdownMsg {
+    Show(HandCount),
+    Step(u32),
+    Continue,
+    SetTheme(String),
+    SetFourColour(bool),
+}
+
+#[derive(Debug)]
+pub enum BreakdownOutput {
+    /// The person has seen the count and the game can carry on.
+    Continue,
+}
+
+impl Breakdown {
+    /// The cards that make up the score being counted. His nob is only
+    /// worth anything because of the up card, so that's picked out too.
+    fn highlighted(&self) -> Vec<Card> {
+        let scoring = match self.counted {
+            0 => return Vec::new(),
+            n => &self.scorings[n - 1],
+        };
+        match scoring {
+            HandScorings::Fifteen(cards)
+            | HandScorings::Pair(cards)
+            | HandScorings::RunOfThree(cards)
+            | HandScorings::RunOfFour(cards)
+            | HandScorings::RunOfFive(cards)
+            | HandScorings::ThreeCardFlush(cards)
+            | HandScorings::FourCardFlush(cards)
+            | HandScorings::FiveCardFlush(cards) => cards.iter().copied().collect(),
+            HandScorings::Nobs(jack) => [Some(*jack), self.up_card].into_iter().flatten().collect(),
+        }
+    }
+
+    /// Everything counted so far, such as "Fifteen 2, fifteen 4, a pair is
+    /// 6".
+    fn calls(&self) -> String {
+        if self.scorings.is_empty() {
+            return String::from("Nothing to count.");
+        }
+        let mut total = 0;
+        let calls = self.scorings[..self.counted]
+            .iter()
+            .map(|scoring| {
+                total += scoring.value();
+                callouts::counting(scoring, total)
+            })
+            .collect::<Vec<String>>();
+        callouts::capitalised(calls.join(", "))
+    }
+
+    fn total(&self) -> u8 {
+        self.scorings[..self.counted]
+            .iter()
+            .map(HandScorings::value)
+            .sum()
+    }
+
+    fn schedule_step(&self, sender: &ComponentSender<Self>) {
+        if self.counted < self.scorings.len() {
+            let sender = sender.clone();
+            let shown = self.shown;
+            glib::timeout_add_local_once(STEP_TIME, move || {
+                sender.input(BreakdownMsg::Step(shown))
+            });
+        }
+    }
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for Breakdown {
+    /// The name of the deck the cards are drawn from.
+    type Init = String;
+    type Input = BreakdownMsg;
+    type Output = BreakdownOutput;
+
+    view! {
+        gtk::Window {
+            set_modal: true,
+            set_hide_on_close: true,
+            set_default_size: (500, 320),
+            #[watch]
+            set_title: Some(&model.title),
+            #[watch]
+            set_visible: !model.hidden,
+            connect_close_request[sender] => move |_| {
+                sender.input(BreakdownMsg::Continue);
+                gtk::Inhibit(true)
+            },
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 10,
+                set_margin_all: 10,
+
+                gtk::Label {
+                    add_css_class: "title-2",
+                    #[watch]
+                    set_label: &model.title,
+                },
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 20,
+                    set_vexpand: true,
+
+                    CardBox {
+                        set_hexpand: true,
+                        set_offset: 60.0,
+                        #[watch]
+                        set_theme: model.theme.as_str(),
+                        #[watch]
+                        set_four_colour: model.four_colour,
+                        #[watch]
+                        set_label: model.title.as_str(),
+                        #[watch]
+                        set_hand: BoxedAnyObject::new(TableCard::face_up(&model.hand)),
+                        #[watch]
+                        set_highlighted: BoxedAnyObject::new(model.highlighted()),
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_width_request: 140,
+
+                        gtk::Label {
+                            set_label: "Up card",
+                        },
+                        CardBox {
+                            set_vexpand: true,
+                            #[watch]
+                            set_theme: model.theme.as_str(),
+                            #[watch]
+                            set_four_colour: model.four_colour,
+                            set_label: "Up card",
+                            #[watch]
+                            set_hand: BoxedAnyObject::new(TableCard::face_up(
+                                &model.up_card.into_iter().collect::<Vec<Card>>(),
+                            )),
+                            #[watch]
+                            set_highlighted: BoxedAnyObject::new(model.highlighted()),
+                        },
+                    },
+                },
+                // The count is read out as it goes along.
+                gtk::Label::builder().accessible_role(gtk::AccessibleRole::Status).build() {
+                    set_wrap: true,
+                    #[watch]
+                    set_label: &model.calls(),
+                },
+                gtk::Label {
+                    add_css_class: "title-1",
+                    #[watch]
+                    set_label: &model.total().to_string(),
+                },
+                #[name = "continue_button"]
+                gtk::Button {
+                    set_label: "Continue",
+                    set_halign: gtk::Align::End,
+                    connect_clicked => BreakdownMsg::Continue,
+                },
+            }
+        }
+    }
+
+    fn init(
+        theme: Self::Init,
+        root: &Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = Breakdown {
+            hidden: true,
+            title: String::new(),
+            hand: Vec::new(),
+            up_card: None,
+            scorings: Vec::new(),
+            counted: 0,
+            shown: 0,
+            theme,
+            four_colour: false,
+        };
+        let widgets = view_output!();
+        root.set_default_widget(Some(&widgets.continue_button));
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            BreakdownMsg::Show(count) => {
+                let mut scorings = count.scorings;
+                scorings.sort_by_key(HandScorings::counting_order);
+                self.title = count.title;
+                self.hand = count.hand;
+                self.up_card = Some(count.up_card);
+                self.scorings = scorings;
+                self.counted = 0;
+                self.shown += 1;
+                self.hidden = false;
+                self.schedule_step(&sender);
+            }
+            BreakdownMsg::Step(shown) => {
+                if shown == self.shown && !self.hidden {
+                    self.counted += 1;
+                    self.schedule_step(&sender);
+                }
+            }
+            BreakdownMsg::Continue => {
+                if !self.hidden {
+                    self.hidden = true;
+                    sender.output(BreakdownOutput::Continue).unwrap_or_default();
+                }
+            }
+            BreakdownMsg::SetTheme(name) => self.theme = name,
+            BreakdownMsg::SetFourColour(four_colour) => self.four_colour = four_colour,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::card_names;
+use gcribbage_lib::deck::Card;
+use gcribbage_lib::scoring::{score_pegging, HandScorings, PeggingScorings};
+use gcribbage_lib::strategy::{DiscardOption, PlayOption};
+
+/// What a player would call out for what their card pegged, such as
+/// "Fifteen for 2, pair for 2". Nothing, if it didn't peg anything.
+pub fn pegging(scorings: &[PeggingScorings]) -> Option<String> {
+    let mut calls = Vec::new();
+    let pairs = scorings
+        .iter()
+        .filter(|s| matches!(s, PeggingScorings::Pair(_)))
+        .count() as u8;
+    for scoring in scorings {
+        let call = match scoring {
+            PeggingScorings::Fifteen => String::from("fifteen for 2"),
+            PeggingScorings::ThirtyOne => String::from("31 for 2"),
+            PeggingScorings::RunOfThree => String::from("run of 3 for 3"),
+            PeggingScorings::RunOfFour => String::from("run of 4 for 4"),
+            PeggingScorings::RunOfFive => String::from("run of 5 for 5"),
+            PeggingScorings::RunOfSix => String::from("run of 6 for 6"),
+            PeggingScorings::RunOfSeven => String::from("run of 7 for 7"),
+            PeggingScorings::LastCard => String::from("last card for 1"),
+            // Three of a kind make three pairs, and four make six, but
+            // they're called out together.
+            PeggingScorings::Pair(_) => continue,
+        };
+        calls.push(call);
+    }
+    match pairs {
+        0 => (),
+        1 => calls.push(String::from("pair for 2")),
+        3 => calls.push(String::from("pair royal for 6")),
+        6 => calls.push(String::from("double pair royal for 12")),
+        n => calls.push(format!("{} pairs for {}", n, n * 2)),
+    }
+    match calls.is_empty() {
+        true => None,
+        false => Some(capitalised(calls.join(", "))),
+    }
+}
+
+/// The call with its first letter made a capital, to start a sentence.
+pub fn capitalised(mut call: String) -> String {
+    if let Some(first) = call.get(..1) {
+        let first = first.to_uppercase();
+        call.replace_range(..1, &first);
+    }
+    call
+}
+
+/// What a player says for one of the scores in a hand as they count it up,
+/// such as "fifteen 4" or "a pair is 6", given the total so far.
+pub fn counting(scoring: &HandScorings, total: u8) -> String {
+    match scoring {
+        HandScorings::Fifteen(_) => format!("fifteen {}", total),
+        HandScorings::Pair(_) => format!("a pair is {}", total),
+        HandScorings::RunOfThree(_) => format!("a run of 3 is {}", total),
+        HandScorings::RunOfFour(_) => format!("a run of 4 is {}", total),
+        HandScorings::RunOfFive(_) => format!("a run of 5 is {}", total),
+        HandScorings::ThreeCardFlush(_)
+        | HandScorings::FourCardFlush(_)
+        | HandScorings::FiveCardFlush(_) => format!("a flush is {}", total),
+        HandScorings::Nobs(_) => format!("one for his nob is {}", total),
+    }
+}
+
+/// A line saying what a discard is worth, such as "2♣ and 9♥: 11.3 (hand
+/// 10.9, crib 0.4)".
+pub fn discard_option(option: &DiscardOption) -> String {
+    format!(
+        "{}: {:.1} (hand {:.1}, crib {:.1})",
+        card_names(&option.discards),
+        option.value,
+        option.hand,
+        option.crib
+    )
+}
+
+/// Why a card is the one to play onto the pile, such as "Play 5♥.
+/// Fifteen for 2."
+pub fn play_reason(option: &PlayOption, played: &[Card]) -> String {
+    let mut pile = played.to_vec();
+    pile.push(option.card);
+    let play = format!("Play {}.", option.card.short_name());
+    let risk = option.risk;
+    match (pegging(&score_pegging(pile)), played.is_empty()) {
+        (Some(call), _) if risk > 0.0 => format!(
+            "{} {}, though it could give away {:.1} on average.",
+            play, call, risk
+        ),
+        (Some(call), _) => format!("{} {}.", play, call),
+        (None, _) if risk == 0.0 => format!("{} Nothing can score off it.", play),
+        (None, true) => format!(
+            "{} It's the safest lead, giving away {:.1} on average.",
+            play, risk
+        ),
+        (None, false) => format!("{} It gives away the least, {:.1} on average.", play, risk),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn called(cards: &str) -> Option<String> {
+        let pile = cards.split(' ').map(Card::from).collect::<Vec<Card>>();
+        pegging(&score_pegging(pile))
+    }
+
+    #[test]
+    fn pegging_is_called_out_together() {
+        assert_eq!(None, called("5H 2D"));
+        assert_eq!(Some(String::from("Fifteen for 2")), called("5H TD"));
+        assert_eq!(Some(String::from("Pair for 2")), called("3H 3D"));
+        assert_eq!(Some(String::from("Pair royal for 6")), called("3H 3D 3S"));
+        assert_eq!(
+            Some(String::from("Fifteen for 2, run of 3 for 3")),
+            called("4H 6D 5S")
+        );
+        assert_eq!(
+            Some(String::from("Double pair royal for 12")),
+            called("2H 2D 2S 2C")
+        );
+    }
+
+    #[test]
+    fn hands_are_counted_aloud() {
+        let cards = HashSet::from(["5H", "TD"].map(Card::from));
+        let calls = [
+            (HandScorings::Fifteen(cards.clone()), 4, "fifteen 4"),
+            (HandScorings::Pair(cards), 6, "a pair is 6"),
+            (
+                HandScorings::Nobs(Card::from("JC")),
+                9,
+                "one for his nob is 9",
+            ),
+        ];
+        for (scoring, total, call) in calls {
+            assert_eq!(call, counting(&scoring, total));
+        }
+        assert_eq!("A pair is 6", capitalised(String::from("a pair is 6")));
+        assert_eq!("", capitalised(String::new()));
+    }
+
+    #[test]
+    fn plays_are_explained() {
+        let option = |card, risk| PlayOption {
+            card: Card::from(card),
+            points: 0,
+            risk,
+        };
+        assert_eq!(
+            "Play 5♥. Fifteen for 2.",
+            play_reason(&option("5H", 0.0), &[Card::from("TD")])
+        );
+        assert_eq!(
+            "Play K♠. Nothing can score off it.",
+            play_reason(&option("KS", 0.0), &[Card::from("TD"), Card::from("5D")])
+        );
+        assert_eq!(
+            "Play 4♣. It's the safest lead, giving away 1.5 on average.",
+            play_reason(&option("4C", 1.5), &[])
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use relm4::gtk::glib;
+use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+
+/// A sidebar listing everything that's happened in the game, in plain
+/// words. Clicking on something shows the table as it was just after.
+pub struct GameLog {
+    revealed: bool,
+    list: gtk::ListBox,
+    scrolled: gtk::ScrolledWindow,
+}
+
+#[derive(Debug)]
+pub enum GameLogMsg {
+    Add(String),
+    Clear,
+    /// Stops picking out the entry that was clicked on, as when going back
+    /// to the game.
+    Unselect,
+    SetRevealed(bool),
+}
+
+#[derive(Debug)]
+pub enum GameLogOutput {
+    /// The entry at this place in the log was clicked on.
+    Selected(usize),
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for GameLog {
+    type Init = ();
+    type Input = GameLogMsg;
+    type Output = GameLogOutput;
+
+    view! {
+        gtk::Revealer {
+            set_transition_type: gtk::RevealerTransitionType::SlideRight,
+            #[watch]
+            set_reveal_child: model.revealed,
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
+                set_margin_all: 5,
+                set_width_request: 260,
+
+                gtk::Label {
+                    set_label: "Game log",
+                    set_xalign: 0.0,
+                    add_css_class: "title-4",
+                },
+                #[local_ref]
+                scrolled -> gtk::ScrolledWindow {
+                    set_vexpand: true,
+                    set_hscrollbar_policy: gtk::PolicyType::Never,
+
+                    #[local_ref]
+                    list -> gtk::ListBox {
+                        set_selection_mode: gtk::SelectionMode::Single,
+                        set_activate_on_single_click: true,
+                        connect_row_activated[sender] => move |_, row| {
+                            let index = row.index().max(0) as usize;
+                            sender.output(GameLogOutput::Selected(index)).unwrap_or_default();
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        _: Self::Init,
+        root: &Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = GameLog {
+            revealed: true,
+            list: gtk::ListBox::new(),
+            scrolled: gtk::ScrolledWindow::new(),
+        };
+        let list = &model.list;
+        let scrolled = &model.scrolled;
+        let widgets = view_output!();
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, _sender: ComponentSender<Self>) {
+        match msg {
+            GameLogMsg::Add(text) => {
+                let label = gtk::Label::new(Some(&text));
+                label.set_xalign(0.0);
+                label.set_wrap(true);
+                label.set_margin_top(2);
+                label.set_margin_bottom(2);
+                self.list.append(&label);
+                // The list only knows how long it is once it's been laid
+                // out again, so it's scrolled to the end after that.
+                let adjustment = self.scrolled.vadjustment();
+                glib::idle_add_local_once(move || {
+                    adjustment.set_value(adjustment.upper() - adjustment.page_size());
+                });
+            }
+            GameLogMsg::Clear => {
+                while let Some(row) = self.list.first_child() {
+                    self.list.remove(&row);
+                }
+            }
+            GameLogMsg::Unselect => self.list.unselect_all(),
+            GameLogMsg::SetRevealed(revealed) => self.revealed = revealed,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use gcribbage_lib::deck::Card;
+use gcribbage_lib::player::KnowsCribbage;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// Stands in for the person at the table.
+///
+/// The game asks its players for their choices when it needs them, but the
+/// person makes theirs by clicking around the window whenever they like. So
+/// the app hands over the person's choices before moving the game along,
+/// and this passes them on when the game asks. Clones share the same
+/// choices.
+#[derive(Clone, Default)]
+pub struct HumanPlayer {
+    choices: Rc<RefCell<Vec<Card>>>,
+}
+
+impl HumanPlayer {
+    pub fn choose(&self, cards: Vec<Card>) {
+        *self.choices.borrow_mut() = cards;
+    }
+}
+
+impl KnowsCribbage for HumanPlayer {
+    fn choose_crib(
+        &mut self,
+        _hand: &HashSet<Card>,
+        _discards: usize,
+        _own_crib: bool,
+    ) -> Vec<Card> {
+        self.choices.borrow_mut().drain(..).collect()
+    }
+
+    fn play(&mut self, _hand: &HashSet<Card>, _played: &[Card]) -> Card {
+        self.choices
+            .borrow_mut()
+            .pop()
+            .expect("The game asked for a play before one was chosen")
+    }
+}
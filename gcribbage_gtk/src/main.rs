@@ -1,58 +1,969 @@
 use relm4::prelude::*;
 use relm4::gtk::prelude::*;
-use gtk::glib::BoxedAnyObject;
+use gtk::glib::{self, BoxedAnyObject};
 use gtk::gio;
+mod breakdown;
+mod callouts;
+mod game_log;
+mod human;
+mod preferences;
+mod records;
+mod review;
+mod settings;
+mod statistics;
+mod theme;
 mod widgets;
 use gcribbage_lib::deck::Card;
-use widgets::CardBox;
+use gcribbage_lib::game::{GameRunner, PlayResult};
+use gcribbage_lib::player::{KnowsCribbage, PlayerPosition};
+use gcribbage_lib::stats::Statistics;
+use gcribbage_lib::strategy;
+use breakdown::{Breakdown, BreakdownMsg, BreakdownOutput, HandCount};
+use game_log::{GameLog, GameLogMsg, GameLogOutput};
+use human::HumanPlayer;
+use preferences::{Preferences, PreferencesMsg, PreferencesOutput};
+use review::{Review, ReviewMsg, Verdict};
+use settings::Settings;
+use statistics::{StatisticsMsg, StatisticsOutput, StatisticsView};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use widgets::{CardBox, CribbageBoard, TableCard};
+
+/// The seat the person sits in. The computer sits in the other one.
+const HUMAN: PlayerPosition = PlayerPosition::First;
+const COMPUTER: PlayerPosition = PlayerPosition::Second;
+
+/// The domain the app's warnings are logged under.
+const LOG_DOMAIN: &str = "gcribbage_gtk";
+
+/// How long a callout stays up.
+const CALLOUT_TIME: Duration = Duration::from_millis(1500);
+
+/// How long to leave things on the table before moving the game along on
+/// its own. That's long enough for a whole hand to finish sliding in at the
+/// usual speed.
+const PAUSE: Duration = Duration::from_millis(800);
+
+/// What the game is waiting on before it can move along.
+#[derive(Debug, PartialEq)]
+enum Waiting {
+    /// Nothing, so it moves itself along.
+    Nothing,
+    /// The person has to choose cards for the crib.
+    Crib,
+    /// The person has to play a card.
+    Play,
+    /// The person can't play, and has to say go.
+    Go,
+    /// The person is looking over how a hand was counted.
+    Count,
+    /// Nothing ever again, because the game is over.
+    GameOver,
+}
+
+/// Everything on the table, as last reported by the game.
+#[derive(Default, Clone)]
+struct Table {
+    hands: HashMap<PlayerPosition, Vec<Card>>,
+    crib: Vec<Card>,
+    up_card: Option<Card>,
+    /// The cards in the middle of the table. That's the pegging pile while
+    /// cards are being played, then each hand in turn as it is counted.
+    pile: Vec<Card>,
+    /// Whose cards went on the pile last, or nobody's if it was the crib.
+    pile_from: Option<PlayerPosition>,
+    /// Whether the pile is being pegged onto, so it has a count.
+    pegging: bool,
+    scores: HashMap<PlayerPosition, u8>,
+    dealer: Option<PlayerPosition>,
+}
+
+impl Table {
+    fn hand(&self, position: PlayerPosition) -> Vec<Card> {
+        self.hands.get(&position).cloned().unwrap_or_default()
+    }
+
+    fn score(&self, position: PlayerPosition) -> u8 {
+        self.scores.get(&position).copied().unwrap_or(0)
+    }
+
+    fn count(&self) -> u8 {
+        self.pile.iter().map(|c| c.rank.value()).sum()
+    }
+
+    /// Puts the pegging pile on the table, remembering who played the last
+    /// card onto it.
+    fn set_pile(&mut self, played: Vec<Card>) {
+        if played.len() > self.pile.len() {
+            let last = played.last();
+            self.pile_from = self
+                .hands
+                .iter()
+                .find(|(_, hand)| last.is_some_and(|card| hand.contains(card)))
+                .map(|(position, _)| *position);
+        }
+        self.pile = played;
+    }
+
+    fn set_hands(&mut self, hands: &HashMap<PlayerPosition, HashSet<Card>>) {
+        self.hands = hands
+            .iter()
+            .map(|(position, hand)| (*position, sorted(hand)))
+            .collect();
+    }
+}
+
+fn sorted(cards: &HashSet<Card>) -> Vec<Card> {
+    let mut cards = cards.iter().copied().collect::<Vec<Card>>();
+    cards.sort();
+    cards
+}
+
+/// The cards written out, such as "2♣ and 9♥".
+fn card_names(cards: &[Card]) -> String {
+    let names = cards.iter().map(Card::short_name).collect::<Vec<String>>();
+    match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => names.concat(),
+    }
+}
+
+/// What the advisor suggests the person does.
+struct Hint {
+    cards: Vec<Card>,
+    /// Why, in a line.
+    status: String,
+    /// What each of the choices is worth, best first, if there are several
+    /// worth going through.
+    options: Vec<String>,
+}
+
+fn who(position: PlayerPosition) -> &'static str {
+    match position == HUMAN {
+        true => "You",
+        false => "The computer",
+    }
+}
+
+/// Who did something, such as "You play" or "The computer plays".
+fn who_does(position: PlayerPosition, verb: &str) -> String {
+    match position == HUMAN {
+        true => format!("You {}", verb),
+        false => format!("The computer {}s", verb),
+    }
+}
+
+/// Sets up a new game between the person and the computer, played by the
+/// rules and against the computer player chosen in the settings.
+///
+/// The game has its own players, so it goes when it's replaced by the next.
+fn new_game(human: &HumanPlayer, settings: &Settings) -> GameRunner<'static> {
+    let players: Vec<Box<dyn KnowsCribbage>> =
+        vec![Box::new(human.clone()), settings.difficulty.player()];
+    GameRunner::owning(players, settings.rules()).expect("The settings are for two players")
+}
 
 struct App {
-    hand: Vec<Card>,
+    runner: GameRunner<'static>,
+    human: HumanPlayer,
+    table: Table,
+    waiting: Waiting,
+    status: String,
+    /// What was just called out, such as "Fifteen for 2".
+    callout: String,
+    /// How many callouts there have been, so clearing away an old one
+    /// doesn't take a newer one with it.
+    callouts: u32,
+    settings: Settings,
+    /// The cards the person was given a hint to pick.
+    hint: Vec<Card>,
+    /// What each choice the hint was picked from is worth.
+    hint_options: Vec<String>,
+    /// How the person's choices this hand compare with the advisor's.
+    verdicts: Vec<Verdict>,
+    /// What's happened since the game's log was last brought up to date.
+    notes: Vec<String>,
+    /// Everything that's happened in the game, with the table as it was
+    /// just after.
+    history: Vec<(String, Table)>,
+    /// Which moment in the history is on the table instead of the game, if
+    /// the person is looking back at one.
+    viewing: Option<usize>,
+    /// How everyone has done, over this game and every one before it.
+    statistics: Statistics,
+    preferences: Controller<Preferences>,
+    breakdown: Controller<Breakdown>,
+    statistics_view: Controller<StatisticsView>,
+    review: Controller<Review>,
+    game_log: Controller<GameLog>,
+}
+
+#[derive(Debug, Clone)]
+enum AppMsg {
+    Step,
+    CardsChosen(Vec<Card>),
+    Go,
+    Hint,
+    NewGame,
+    ShowPreferences,
+    ShowStatistics,
+    ResetStatistics,
+    SetSettings(Settings),
+    ShowLog(bool),
+    /// Puts the table as it was at this moment in the history on show, or
+    /// the game again.
+    View(Option<usize>),
+    ClearCallout(u32),
+    Counted,
+}
+
+impl App {
+    /// Adds a line to the game's log.
+    fn note(&mut self, line: String) {
+        self.notes.push(line);
+    }
+
+    /// The table that's on show, which is the game's unless the person is
+    /// looking back at an earlier moment.
+    fn shown(&self) -> &Table {
+        match self.viewing.and_then(|i| self.history.get(i)) {
+            Some((_, table)) => table,
+            None => &self.table,
+        }
+    }
+
+    /// What the person is looking back at, if anything.
+    fn viewing(&self) -> String {
+        match self.viewing.and_then(|i| self.history.get(i)) {
+            Some((line, _)) => format!("Just after: {}", line),
+            None => String::new(),
+        }
+    }
+
+    fn call_out(&mut self, position: PlayerPosition, call: &str) {
+        self.callout = format!("{}: {}", who(position), call);
+        self.callouts += 1;
+    }
+
+    /// Puts what the game just did on the table, and works out whether the
+    /// person needs to do something before the game can carry on.
+    fn show(&mut self, result: PlayResult) {
+        let discards = self.runner.rules().discards;
+        self.waiting = Waiting::Nothing;
+        self.hint.clear();
+        self.hint_options.clear();
+        match result {
+            PlayResult::DealerChosen(info) => {
+                self.table = Table::default();
+                self.table.dealer = Some(info.dealer);
+                self.note(format!(
+                    "You cut {}, the computer cuts {}. {}.",
+                    info.chosen_cards[&HUMAN].short_name(),
+                    info.chosen_cards[&COMPUTER].short_name(),
+                    who_does(info.dealer, "deal"),
+                ));
+                self.status = format!(
+                    "You cut {}, the computer cut {}. {} deal first.",
+                    info.chosen_cards[&HUMAN].short_name(),
+                    info.chosen_cards[&COMPUTER].short_name(),
+                    who(info.dealer),
+                );
+            }
+            PlayResult::CardsDealt(info) => {
+                self.table.set_hands(&info.hands);
+                self.table.dealer = Some(info.dealer);
+                self.table.scores = info.scores;
+                self.table.crib.clear();
+                self.table.pile.clear();
+                self.table.up_card = None;
+                self.table.pegging = false;
+                self.verdicts.clear();
+                self.note(format!("New hand. {}.", who_does(info.dealer, "deal")));
+                self.waiting = Waiting::Crib;
+                self.status = match info.dealer == HUMAN {
+                    true => format!("Choose {} cards for your crib.", discards),
+                    false => format!("Choose {} cards for the computer's crib.", discards),
+                };
+            }
+            PlayResult::CribChosen(info) => {
+                self.table.set_hands(&info.hands);
+                self.table.crib = sorted(&info.crib);
+                self.table.scores = info.scores;
+                self.note(String::from("The crib is laid away."));
+                self.status = String::from("Cutting for the up card.");
+            }
+            PlayResult::WaitingForPlay(info) => {
+                let played = info.played.len() > self.table.pile.len();
+                self.table.set_pile(info.played);
+                self.table.set_hands(&info.hands);
+                if self.table.up_card.is_none() {
+                    self.table.up_card = Some(info.up_card);
+                    self.note(format!("The up card is {}.", info.up_card.short_name()));
+                    let dealer = self.table.dealer.unwrap_or(COMPUTER);
+                    let scored = info.scores.get(&dealer).copied().unwrap_or(0);
+                    let heels = scored.saturating_sub(self.table.score(dealer));
+                    if heels > 0 {
+                        self.note(format!("{} {} for heels.", who_does(dealer, "take"), heels));
+                    }
+                }
+                self.table.scores = info.scores;
+                self.table.pegging = true;
+                let call = callouts::pegging(&info.scorings);
+                if let (Some(call), Some(position)) = (&call, self.table.pile_from) {
+                    self.call_out(position, call);
+                }
+                if let (true, Some(position), Some(card)) =
+                    (played, self.table.pile_from, self.table.pile.last())
+                {
+                    let play = format!(
+                        "{} {} for {}",
+                        who_does(position, "play"),
+                        card.short_name(),
+                        self.table.count()
+                    );
+                    self.note(match call {
+                        Some(call) => format!("{}. {}.", play, call),
+                        None => format!("{}.", play),
+                    });
+                }
+                let count = self.table.count();
+                let can_play = self
+                    .table
+                    .hand(HUMAN)
+                    .iter()
+                    .any(|c| count + c.rank.value() <= 31);
+                let has_cards = !self.table.hand(HUMAN).is_empty();
+                if info.current_player == HUMAN && can_play {
+                    self.waiting = Waiting::Play;
+                    self.status = String::from("Play a card.");
+                } else if info.current_player == HUMAN && has_cards {
+                    self.waiting = Waiting::Go;
+                    self.status = String::from("You can't play without going over 31. Say go.");
+                } else {
+                    self.status = format!("{} to play.", who(info.current_player));
+                }
+            }
+            PlayResult::Go(info) => {
+                self.table.set_pile(info.played);
+                self.table.set_hands(&info.hands);
+                self.table.scores = info.scores;
+                self.call_out(info.caller, "Go");
+                self.note(format!("{}: Go.", who(info.caller)));
+                self.status = format!("{} said go.", who(info.caller));
+            }
+            PlayResult::WaitingForLastCardAcknowledgement(info) => {
+                self.table.set_hands(&info.hands);
+                let pegged = info
+                    .scores
+                    .iter()
+                    .find(|(position, score)| **score > self.table.score(**position))
+                    .map(|(position, _)| *position);
+                if let (Some(position), Some(call)) = (pegged, callouts::pegging(&info.scorings)) {
+                    self.call_out(position, &call);
+                    self.note(format!("{}: {}.", who(position), call));
+                }
+                self.table.scores = info.scores;
+                self.status = String::from("Last card. The count starts again.");
+            }
+            PlayResult::HandScore(info) => {
+                let total: u8 = info
+                    .scorings
+                    .iter()
+                    .flatten()
+                    .map(|s| s.value())
+                    .sum();
+                let hand = sorted(&info.hand);
+                let whose = match (hand == self.table.crib, info.player == HUMAN) {
+                    (true, true) => "Your crib",
+                    (true, false) => "The computer's crib",
+                    (false, true) => "Your hand",
+                    (false, false) => "The computer's hand",
+                };
+                self.status = format!("{} scores {}.", whose, total);
+                self.note(format!("{}: {} points.", whose, total));
+                let scorings = info.scorings.filter(|_| !self.settings.auto_count);
+                if let Some(scorings) = scorings {
+                    self.waiting = Waiting::Count;
+                    self.breakdown.emit(BreakdownMsg::Show(HandCount {
+                        title: String::from(whose),
+                        hand: hand.clone(),
+                        up_card: info.up_card,
+                        scorings,
+                    }));
+                }
+                // Once the crib's been counted, the hand is over and can be
+                // gone back over.
+                if hand == self.table.crib && self.settings.advisor && !self.verdicts.is_empty() {
+                    let verdicts = std::mem::take(&mut self.verdicts);
+                    self.review.emit(ReviewMsg::Show(verdicts));
+                }
+                self.table.pile_from = match hand == self.table.crib {
+                    true => None,
+                    false => Some(info.player),
+                };
+                self.table.pile = hand;
+                self.table.pegging = false;
+                self.table.scores = info.scores;
+            }
+            PlayResult::GameOver(result) => {
+                self.table.scores = result.scores;
+                self.waiting = Waiting::GameOver;
+                self.status = match result.winner == HUMAN {
+                    true => String::from("You win!"),
+                    false => String::from("The computer wins."),
+                };
+                self.note(format!(
+                    "{}, {} to {}.",
+                    who_does(result.winner, "win"),
+                    self.table.score(result.winner),
+                    self.table.score(result.winner.next(2)),
+                ));
+            }
+            PlayResult::Incomplete => self.waiting = Waiting::GameOver,
+        }
+    }
+
+    fn count(&self) -> String {
+        match self.shown().pegging {
+            true => format!("Count: {}", self.shown().count()),
+            false => String::new(),
+        }
+    }
+
+    fn scoreboard(&self) -> String {
+        format!(
+            "You: {}    Computer: {}",
+            self.shown().score(HUMAN),
+            self.shown().score(COMPUTER)
+        )
+    }
+
+    /// The deck, with the up card on top of it. That's turned over once
+    /// it's been cut.
+    fn deck(&self) -> Vec<TableCard> {
+        let top = self.shown().up_card.map_or(TableCard::FaceDown, TableCard::FaceUp);
+        vec![TableCard::FaceDown, top]
+    }
+
+    /// What the strongest computer player would pick in the person's
+    /// place, a line saying why, and what each of the other choices is
+    /// worth.
+    fn hint(&self) -> Option<Hint> {
+        let hand = self.table.hand(HUMAN).into_iter().collect();
+        match self.waiting {
+            Waiting::Crib => {
+                let discards = self.runner.rules().discards;
+                let own_crib = self.table.dealer == Some(HUMAN);
+                let options = strategy::discard_options(&hand, discards, own_crib);
+                let best = options.first()?;
+                let status = format!(
+                    "Put {} in the crib. That's worth {:.1} points on average.",
+                    card_names(&best.discards),
+                    best.value
+                );
+                Some(Hint {
+                    cards: best.discards.clone(),
+                    status,
+                    options: options.iter().map(callouts::discard_option).collect(),
+                })
+            }
+            Waiting::Play => {
+                let options = strategy::play_options(&hand, &self.table.pile);
+                let best = options.first()?;
+                Some(Hint {
+                    cards: vec![best.card],
+                    status: callouts::play_reason(best, &self.table.pile),
+                    options: Vec::new(),
+                })
+            }
+            Waiting::Nothing | Waiting::Go | Waiting::Count | Waiting::GameOver => None,
+        }
+    }
+
+    /// How the cards the person just chose compare with what the advisor
+    /// would have chosen.
+    fn verdict(&self, cards: &[Card]) -> Option<Verdict> {
+        let hand = self.table.hand(HUMAN).into_iter().collect();
+        match self.waiting {
+            Waiting::Crib => Verdict::discard(&hand, cards, self.table.dealer == Some(HUMAN)),
+            Waiting::Play => Verdict::play(&hand, &self.table.pile, *cards.first()?),
+            Waiting::Nothing | Waiting::Go | Waiting::Count | Waiting::GameOver => None,
+        }
+    }
+
+    /// How many of the person's cards they need to click on.
+    fn choose_count(&self) -> u32 {
+        if self.viewing.is_some() {
+            return 0;
+        }
+        match self.waiting {
+            Waiting::Crib => self.runner.rules().discards as u32,
+            Waiting::Play => 1,
+            Waiting::Nothing | Waiting::Go | Waiting::Count | Waiting::GameOver => 0,
+        }
+    }
 }
 
 #[relm4::component]
 impl SimpleComponent for App {
-    type Init = Vec<Card>;
-    type Input = ();
+    type Init = Settings;
+    type Input = AppMsg;
     type Output = ();
 
     view! {
         gtk::Window {
-            set_title: Some("Simple App"),
+            set_title: Some("Cribbage"),
             set_default_size: (800, 600),
+            #[wrap(Some)]
+            set_titlebar = &gtk::HeaderBar {
+                pack_end = &gtk::Button {
+                    set_icon_name: "preferences-system-symbolic",
+                    set_tooltip_text: Some("Preferences"),
+                    connect_clicked => AppMsg::ShowPreferences,
+                },
+                pack_end = &gtk::Button {
+                    set_icon_name: "view-list-symbolic",
+                    set_tooltip_text: Some("Statistics"),
+                    connect_clicked => AppMsg::ShowStatistics,
+                },
+                pack_start = &gtk::ToggleButton {
+                    set_icon_name: "sidebar-show-symbolic",
+                    set_tooltip_text: Some("Game log"),
+                    set_active: true,
+                    connect_toggled[sender] => move |button| {
+                        sender.input(AppMsg::ShowLog(button.is_active()));
+                    },
+                },
+            },
 
             gtk::Box {
-                set_orientation: gtk::Orientation::Vertical,
-                set_spacing: 5,
-                set_margin_all: 5,
-                set_homogeneous: true,
+                set_orientation: gtk::Orientation::Horizontal,
 
-                CardBox {
-                    set_hand: BoxedAnyObject::new(vec![Card::from("as")]),
-                },
-                CardBox {
-                    set_hand: BoxedAnyObject::new(vec![Card::from("ah"), Card::from("2s"), Card::from("qd"), Card::from("9c")]),
-                    set_offset: 50.0,
+                #[local_ref]
+                log_panel -> gtk::Revealer {},
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_hexpand: true,
+                    set_spacing: 5,
+                    set_margin_all: 5,
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 10,
+                        set_halign: gtk::Align::Center,
+                        #[watch]
+                        set_visible: model.viewing.is_some(),
+
+                        gtk::Label {
+                            add_css_class: "dim-label",
+                            #[watch]
+                            set_label: &model.viewing(),
+                        },
+                        gtk::Button {
+                            set_label: "Back to the game",
+                            connect_clicked => AppMsg::View(None),
+                        },
+                    },
+                    CribbageBoard {
+                        set_height_request: 150,
+                        #[watch]
+                        set_target_score: model.runner.rules().target_score,
+                        #[watch]
+                        set_skunk_line: model.runner.rules().skunk_line,
+                        #[watch]
+                        set_double_skunk_line: model.runner.rules().double_skunk_line,
+                        #[watch]
+                        set_animation_time: model.settings.animation.time(),
+                        #[watch]
+                        set_scores: BoxedAnyObject::new(model.shown().scores.clone()),
+                    },
+                    gtk::Label {
+                        #[watch]
+                        set_label: &model.scoreboard(),
+                    },
+                    #[name = "computer_hand"]
+                    CardBox {
+                        #[watch]
+                        set_theme: model.settings.theme.as_str(),
+                        #[watch]
+                        set_four_colour: model.settings.four_colour,
+                        #[watch]
+                        set_animation_time: model.settings.animation.time(),
+                        set_label: "The computer's hand",
+                        set_vexpand: true,
+                        set_origin: Some(deck.clone()),
+                        #[watch]
+                        set_hand: BoxedAnyObject::new(TableCard::face_down(&model.shown().hand(COMPUTER))),
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 5,
+                        set_homogeneous: true,
+                        set_vexpand: true,
+
+                        #[name = "deck"]
+                        CardBox {
+                            #[watch]
+                            set_theme: model.settings.theme.as_str(),
+                            #[watch]
+                            set_four_colour: model.settings.four_colour,
+                            #[watch]
+                            set_animation_time: model.settings.animation.time(),
+                            set_label: "The deck",
+                            #[watch]
+                            set_hand: BoxedAnyObject::new(model.deck()),
+                        },
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+
+                            gtk::Label {
+                                add_css_class: "title-4",
+                                #[watch]
+                                set_label: &model.count(),
+                            },
+                            CardBox {
+                                set_vexpand: true,
+                                #[watch]
+                                set_theme: model.settings.theme.as_str(),
+                                #[watch]
+                                set_four_colour: model.settings.four_colour,
+                                #[watch]
+                                set_animation_time: model.settings.animation.time(),
+                                set_label: "The table",
+                                #[watch]
+                                set_origin: Some(match model.shown().pile_from {
+                                    Some(HUMAN) => human_hand.clone(),
+                                    Some(_) => computer_hand.clone(),
+                                    None => crib.clone(),
+                                }),
+                                #[watch]
+                                set_hand: BoxedAnyObject::new(TableCard::face_up(&model.shown().pile)),
+                            },
+                            // What's scored is read out as it's called.
+                            gtk::Label::builder().accessible_role(gtk::AccessibleRole::Status).build() {
+                                add_css_class: "title-2",
+                                #[watch]
+                                set_label: &model.callout,
+                            },
+                        },
+                        #[name = "crib"]
+                        CardBox {
+                            #[watch]
+                            set_theme: model.settings.theme.as_str(),
+                            #[watch]
+                            set_four_colour: model.settings.four_colour,
+                            #[watch]
+                            set_animation_time: model.settings.animation.time(),
+                            set_label: "The crib",
+                            set_origin: Some(human_hand.clone()),
+                            #[watch]
+                            set_hand: BoxedAnyObject::new(TableCard::face_down(&model.shown().crib)),
+                        },
+                    },
+                    #[name = "human_hand"]
+                    CardBox {
+                        #[watch]
+                        set_theme: model.settings.theme.as_str(),
+                        #[watch]
+                        set_four_colour: model.settings.four_colour,
+                        #[watch]
+                        set_animation_time: model.settings.animation.time(),
+                        set_label: "Your hand",
+                        set_vexpand: true,
+                        set_offset: 50.0,
+                        set_origin: Some(deck.clone()),
+                        set_selectable: true,
+                        #[watch]
+                        set_hand: BoxedAnyObject::new(TableCard::face_up(&model.shown().hand(HUMAN))),
+                        #[watch]
+                        set_choose_count: model.choose_count(),
+                        #[watch]
+                        set_highlighted: BoxedAnyObject::new(model.hint.clone()),
+                        connect_cards_chosen[sender] => move |_, cards| {
+                            sender.input(AppMsg::CardsChosen(cards));
+                        },
+                    },
+                    // So is whose turn it is, and what they have to do.
+                    gtk::Label::builder().accessible_role(gtk::AccessibleRole::Status).build() {
+                        #[watch]
+                        set_label: &model.status,
+                    },
+                    gtk::Expander {
+                        set_label: Some("Every choice"),
+                        set_halign: gtk::Align::Center,
+                        #[watch]
+                        set_visible: !model.hint_options.is_empty(),
+
+                        gtk::Label {
+                            add_css_class: "monospace",
+                            #[watch]
+                            set_label: &model.hint_options.join("\n"),
+                        },
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 5,
+                        set_halign: gtk::Align::Center,
+
+                        gtk::Button {
+                            set_label: "Go",
+                            set_tooltip_text: Some("Say go (G)"),
+                            #[watch]
+                            set_visible: model.waiting == Waiting::Go,
+                            connect_clicked => AppMsg::Go,
+                        },
+                        gtk::Button {
+                            set_label: "Hint",
+                            set_tooltip_text: Some("Show what the computer would do (H)"),
+                            #[watch]
+                            set_visible: model.settings.advisor
+                                && matches!(model.waiting, Waiting::Crib | Waiting::Play),
+                            connect_clicked => AppMsg::Hint,
+                        },
+                        gtk::Button {
+                            set_label: "New game",
+                            set_tooltip_text: Some("Start a new game (Ctrl+N)"),
+                            connect_clicked => AppMsg::NewGame,
+                        },
+                    },
                 },
+                #[local_ref]
+                review_panel -> gtk::Revealer {},
             }
         }
     }
 
     fn init(
-        hand: Self::Init,
+        settings: Self::Init,
         root: &Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
-        let model = App { hand };
+        let human = HumanPlayer::default();
+        let preferences = Preferences::builder()
+            .transient_for(root)
+            .launch(settings.clone())
+            .forward(sender.input_sender(), |output| match output {
+                PreferencesOutput::Changed(settings) => AppMsg::SetSettings(settings),
+            });
+        let breakdown = Breakdown::builder()
+            .transient_for(root)
+            .launch(settings.theme.clone())
+            .forward(sender.input_sender(), |output| match output {
+                BreakdownOutput::Continue => AppMsg::Counted,
+            });
+        breakdown.emit(BreakdownMsg::SetFourColour(settings.four_colour));
+        let statistics_view = StatisticsView::builder()
+            .transient_for(root)
+            .launch(settings.theme.clone())
+            .forward(sender.input_sender(), |output| match output {
+                StatisticsOutput::Reset => AppMsg::ResetStatistics,
+            });
+        statistics_view.emit(StatisticsMsg::SetFourColour(settings.four_colour));
+        let review = Review::builder().launch(()).detach();
+        let game_log = GameLog::builder()
+            .launch(())
+            .forward(sender.input_sender(), |output| match output {
+                GameLogOutput::Selected(i) => AppMsg::View(Some(i)),
+            });
+        let runner = new_game(&human, &settings);
+        let statistics = Statistics::with_players(records::load(), runner.rules());
+        let model = App {
+            runner,
+            human,
+            table: Table::default(),
+            waiting: Waiting::Nothing,
+            status: String::new(),
+            callout: String::new(),
+            callouts: 0,
+            settings,
+            hint: Vec::new(),
+            hint_options: Vec::new(),
+            verdicts: Vec::new(),
+            notes: Vec::new(),
+            history: Vec::new(),
+            viewing: None,
+            statistics,
+            preferences,
+            breakdown,
+            statistics_view,
+            review,
+            game_log,
+        };
+        let review_panel = model.review.widget();
+        let log_panel = model.game_log.widget();
         let widgets = view_output!();
+
+        let shortcuts = gtk::ShortcutController::new();
+        for (trigger, msg) in [
+            ("g", AppMsg::Go),
+            ("h", AppMsg::Hint),
+            ("<Control>n", AppMsg::NewGame),
+        ] {
+            let sender = sender.clone();
+            shortcuts.add_shortcut(shortcut(trigger, move || sender.input(msg.clone())));
+        }
+        root.add_controller(shortcuts);
+        GtkWindowExt::set_focus(root, Some(&widgets.human_hand));
+
+        sender.input(AppMsg::Step);
         ComponentParts { model, widgets }
     }
 
-    fn update(&mut self, msg: Self::Input, _sender: ComponentSender<Self>) {}
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            AppMsg::Step => {
+                // A step might have been scheduled before the person was
+                // asked to do something. It has to wait for them.
+                if self.waiting != Waiting::Nothing {
+                    return;
+                }
+                let callouts = self.callouts;
+                let result = self.runner.step();
+                self.statistics.record(&result);
+                let saved = match result {
+                    PlayResult::HandScore(_) | PlayResult::GameOver(_) => {
+                        records::save(&self.statistics.players)
+                    }
+                    _ => Ok(()),
+                };
+                self.show(result);
+                if let Err(error) = saved {
+                    self.status = format!("Could not save the statistics: {}", error);
+                }
+                for line in std::mem::take(&mut self.notes) {
+                    self.history.push((line.clone(), self.table.clone()));
+                    self.game_log.emit(GameLogMsg::Add(line));
+                }
+                if self.callouts != callouts {
+                    let callout = self.callouts;
+                    let sender = sender.clone();
+                    glib::timeout_add_local_once(CALLOUT_TIME, move || {
+                        sender.input(AppMsg::ClearCallout(callout))
+                    });
+                }
+                if self.waiting == Waiting::Nothing {
+                    // Slower animations need longer to finish, but turning
+                    // them off doesn't make the computer play any faster.
+                    let pause = PAUSE.mul_f64(self.settings.animation.time().max(1.0));
+                    glib::timeout_add_local_once(pause, move || sender.input(AppMsg::Step));
+                }
+            }
+            AppMsg::CardsChosen(cards) => {
+                if self.waiting == Waiting::Play {
+                    let card = cards[0];
+                    if self.table.count() + card.rank.value() > 31 {
+                        self.status = format!("{} would go over 31.", card.short_name());
+                        return;
+                    }
+                }
+                if self.waiting == Waiting::Crib || self.waiting == Waiting::Play {
+                    if self.settings.advisor {
+                        self.verdicts.extend(self.verdict(&cards));
+                    }
+                    self.hint.clear();
+                    self.hint_options.clear();
+                    self.human.choose(cards);
+                    self.waiting = Waiting::Nothing;
+                    sender.input(AppMsg::Step);
+                }
+            }
+            AppMsg::Go => {
+                if self.waiting == Waiting::Go {
+                    self.waiting = Waiting::Nothing;
+                    sender.input(AppMsg::Step);
+                }
+            }
+            AppMsg::Hint => {
+                if !self.settings.advisor {
+                    return;
+                }
+                if let Some(hint) = self.hint() {
+                    self.hint = hint.cards;
+                    self.status = hint.status;
+                    self.hint_options = hint.options;
+                }
+            }
+            AppMsg::ClearCallout(callout) => {
+                if callout == self.callouts {
+                    self.callout.clear();
+                }
+            }
+            AppMsg::ShowPreferences => self.preferences.emit(PreferencesMsg::Show),
+            AppMsg::ShowLog(shown) => self.game_log.emit(GameLogMsg::SetRevealed(shown)),
+            AppMsg::View(moment) => {
+                self.viewing = moment.filter(|i| *i < self.history.len());
+                if self.viewing.is_none() {
+                    self.game_log.emit(GameLogMsg::Unselect);
+                }
+            }
+            AppMsg::ShowStatistics => {
+                let players = self.statistics.players.clone();
+                self.statistics_view.emit(StatisticsMsg::Show(players));
+            }
+            AppMsg::ResetStatistics => {
+                self.statistics.players.clear();
+                if let Err(error) = records::save(&self.statistics.players) {
+                    self.status = format!("Could not save the statistics: {}", error);
+                }
+            }
+            AppMsg::SetSettings(settings) => {
+                if let Err(error) = settings.save() {
+                    self.status = format!("Could not save the settings: {}", error);
+                }
+                self.breakdown
+                    .emit(BreakdownMsg::SetTheme(settings.theme.clone()));
+                self.breakdown
+                    .emit(BreakdownMsg::SetFourColour(settings.four_colour));
+                self.statistics_view
+                    .emit(StatisticsMsg::SetTheme(settings.theme.clone()));
+                self.statistics_view
+                    .emit(StatisticsMsg::SetFourColour(settings.four_colour));
+                if !settings.advisor {
+                    self.hint.clear();
+                    self.hint_options.clear();
+                    self.review.emit(ReviewMsg::Hide);
+                }
+                self.settings = settings;
+            }
+            AppMsg::Counted => {
+                if self.waiting == Waiting::Count {
+                    self.waiting = Waiting::Nothing;
+                    sender.input(AppMsg::Step);
+                }
+            }
+            AppMsg::NewGame => {
+                self.runner = new_game(&self.human, &self.settings);
+                self.statistics.new_game(self.runner.rules());
+                self.table = Table::default();
+                self.callout.clear();
+                self.hint.clear();
+                self.hint_options.clear();
+                self.verdicts.clear();
+                self.review.emit(ReviewMsg::Hide);
+                self.history.clear();
+                self.viewing = None;
+                self.game_log.emit(GameLogMsg::Clear);
+                self.waiting = Waiting::Nothing;
+                sender.input(AppMsg::Step);
+            }
+        }
+    }
+}
+
+/// A keyboard shortcut, from a trigger such as "<Control>n", that calls
+/// the given function.
+fn shortcut<F: Fn() + 'static>(trigger: &str, f: F) -> gtk::Shortcut {
+    gtk::Shortcut::new(
+        gtk::ShortcutTrigger::parse_string(trigger),
+        Some(gtk::CallbackAction::new(move |_, _| {
+            f();
+            true
+        })),
+    )
 }
 
 fn main() {
     gio::resources_register_include!("resources.gresource").expect("Failed to register resources");
-    let app = RelmApp::new("relm4.example.simple");
-    app.run::<App>(vec![]);
+    let app = RelmApp::new("com.ronsbrain.gcribbage_gtk");
+    app.run::<App>(Settings::load());
 }
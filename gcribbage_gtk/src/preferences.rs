@@ -0,0 +1,249 @@
+use crate::callouts::capitalised;
+use crate::settings::{AnimationSpeed, Settings, Variant, TARGET_SCORES};
+use crate::theme::DeckTheme;
+use gcribbage_lib::strategy::Difficulty;
+use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+
+/// A window for choosing how the app looks and plays.
+pub struct Preferences {
+    hidden: bool,
+    settings: Settings,
+    themes: Vec<DeckTheme>,
+}
+
+#[derive(Debug)]
+pub enum PreferencesMsg {
+    Show,
+    Hide,
+    DifficultySelected(u32),
+    VariantSelected(u32),
+    TargetSelected(u32),
+    CribFourCardFlush(bool),
+    AutoCount(bool),
+    Advisor(bool),
+    ThemeSelected(u32),
+    FourColour(bool),
+    AnimationSelected(u32),
+}
+
+#[derive(Debug)]
+pub enum PreferencesOutput {
+    /// The settings, after something about them was changed.
+    Changed(Settings),
+}
+
+/// The names to show in a drop down list of options, and which of them is
+/// the one that's chosen.
+fn choices<T: PartialEq>(
+    options: impl Iterator<Item = T>,
+    chosen: &T,
+    name: impl Fn(&T) -> String,
+) -> (gtk::StringList, u32) {
+    let names = gtk::StringList::new(&[]);
+    let mut selected = 0;
+    for (i, option) in options.enumerate() {
+        names.append(&name(&option));
+        if option == *chosen {
+            selected = i as u32;
+        }
+    }
+    (names, selected)
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for Preferences {
+    type Init = Settings;
+    type Input = PreferencesMsg;
+    type Output = PreferencesOutput;
+
+    view! {
+        gtk::Window {
+            set_title: Some("Preferences"),
+            set_modal: true,
+            set_hide_on_close: true,
+            #[watch]
+            set_visible: !model.hidden,
+            connect_close_request[sender] => move |_| {
+                sender.input(PreferencesMsg::Hide);
+                gtk::Inhibit(true)
+            },
+
+            gtk::Grid {
+                set_margin_all: 10,
+                set_row_spacing: 5,
+                set_column_spacing: 10,
+
+                attach[0, 0, 1, 1] = &gtk::Label {
+                    set_label: "Computer player",
+                    set_halign: gtk::Align::Start,
+                },
+                attach[1, 0, 1, 1] = &gtk::DropDown {
+                    set_model: Some(&difficulties.0),
+                    set_selected: difficulties.1,
+                    connect_selected_notify[sender] => move |choice| {
+                        sender.input(PreferencesMsg::DifficultySelected(choice.selected()));
+                    },
+                },
+                attach[0, 1, 1, 1] = &gtk::Label {
+                    set_label: "Cards dealt",
+                    set_halign: gtk::Align::Start,
+                },
+                attach[1, 1, 1, 1] = &gtk::DropDown {
+                    set_model: Some(&variants.0),
+                    set_selected: variants.1,
+                    connect_selected_notify[sender] => move |choice| {
+                        sender.input(PreferencesMsg::VariantSelected(choice.selected()));
+                    },
+                },
+                attach[0, 2, 1, 1] = &gtk::Label {
+                    set_label: "Game to",
+                    set_halign: gtk::Align::Start,
+                },
+                attach[1, 2, 1, 1] = &gtk::DropDown {
+                    set_model: Some(&targets.0),
+                    set_selected: targets.1,
+                    connect_selected_notify[sender] => move |choice| {
+                        sender.input(PreferencesMsg::TargetSelected(choice.selected()));
+                    },
+                },
+                attach[0, 3, 2, 1] = &gtk::CheckButton {
+                    set_label: Some("Four-card flushes count in the crib"),
+                    set_active: model.settings.crib_four_card_flush,
+                    connect_toggled[sender] => move |check| {
+                        sender.input(PreferencesMsg::CribFourCardFlush(check.is_active()));
+                    },
+                },
+                attach[0, 4, 2, 1] = &gtk::CheckButton {
+                    set_label: Some("Count hands automatically"),
+                    set_active: model.settings.auto_count,
+                    connect_toggled[sender] => move |check| {
+                        sender.input(PreferencesMsg::AutoCount(check.is_active()));
+                    },
+                },
+                attach[0, 5, 2, 1] = &gtk::CheckButton {
+                    set_label: Some("Offer hints"),
+                    set_active: model.settings.advisor,
+                    connect_toggled[sender] => move |check| {
+                        sender.input(PreferencesMsg::Advisor(check.is_active()));
+                    },
+                },
+                attach[0, 6, 1, 1] = &gtk::Label {
+                    set_label: "Card deck",
+                    set_halign: gtk::Align::Start,
+                },
+                attach[1, 6, 1, 1] = &gtk::DropDown {
+                    set_model: Some(&decks.0),
+                    set_selected: decks.1,
+                    connect_selected_notify[sender] => move |decks| {
+                        sender.input(PreferencesMsg::ThemeSelected(decks.selected()));
+                    },
+                },
+                attach[0, 7, 2, 1] = &gtk::CheckButton {
+                    set_label: Some("High contrast four-colour suits"),
+                    set_active: model.settings.four_colour,
+                    connect_toggled[sender] => move |check| {
+                        sender.input(PreferencesMsg::FourColour(check.is_active()));
+                    },
+                },
+                attach[0, 8, 1, 1] = &gtk::Label {
+                    set_label: "Animations",
+                    set_halign: gtk::Align::Start,
+                },
+                attach[1, 8, 1, 1] = &gtk::DropDown {
+                    set_model: Some(&speeds.0),
+                    set_selected: speeds.1,
+                    connect_selected_notify[sender] => move |choice| {
+                        sender.input(PreferencesMsg::AnimationSelected(choice.selected()));
+                    },
+                },
+                attach[0, 9, 2, 1] = &gtk::Label {
+                    set_label: "Changes to the computer player and the rules start with the next game.",
+                    set_wrap: true,
+                    set_max_width_chars: 40,
+                    add_css_class: "dim-label",
+                },
+            }
+        }
+    }
+
+    fn init(
+        settings: Self::Init,
+        root: &Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let themes = DeckTheme::available();
+        let difficulties = choices(Difficulty::iter(), &settings.difficulty, |d| {
+            capitalised(String::from(d.name()))
+        });
+        let variants = choices(Variant::iter(), &settings.variant, |v| {
+            String::from(match v {
+                Variant::SixCard => "Six cards",
+                Variant::FiveCard => "Five cards",
+            })
+        });
+        let targets = choices(
+            TARGET_SCORES.into_iter(),
+            &settings.target_score,
+            u8::to_string,
+        );
+        let names = themes.iter().map(|theme| theme.name.clone());
+        let decks = choices(names, &settings.theme, String::clone);
+        let speeds = choices(AnimationSpeed::iter(), &settings.animation, |a| {
+            capitalised(String::from(a.name()))
+        });
+        let model = Preferences {
+            hidden: true,
+            settings,
+            themes,
+        };
+        let widgets = view_output!();
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        let settings = &mut self.settings;
+        match msg {
+            PreferencesMsg::Show => {
+                self.hidden = false;
+                return;
+            }
+            PreferencesMsg::Hide => {
+                self.hidden = true;
+                return;
+            }
+            PreferencesMsg::DifficultySelected(i) => {
+                if let Some(difficulty) = Difficulty::iter().nth(i as usize) {
+                    settings.difficulty = difficulty;
+                }
+            }
+            PreferencesMsg::VariantSelected(i) => {
+                if let Some(variant) = Variant::iter().nth(i as usize) {
+                    settings.variant = variant;
+                }
+            }
+            PreferencesMsg::TargetSelected(i) => {
+                if let Some(target) = TARGET_SCORES.get(i as usize) {
+                    settings.target_score = *target;
+                }
+            }
+            PreferencesMsg::ThemeSelected(i) => {
+                if let Some(theme) = self.themes.get(i as usize) {
+                    settings.theme = theme.name.clone();
+                }
+            }
+            PreferencesMsg::AnimationSelected(i) => {
+                if let Some(speed) = AnimationSpeed::iter().nth(i as usize) {
+                    settings.animation = speed;
+                }
+            }
+            PreferencesMsg::CribFourCardFlush(on) => settings.crib_four_card_flush = on,
+            PreferencesMsg::AutoCount(on) => settings.auto_count = on,
+            PreferencesMsg::Advisor(on) => settings.advisor = on,
+            PreferencesMsg::FourColour(on) => settings.four_colour = on,
+        }
+        sender
+            .output(PreferencesOutput::Changed(self.settings.clone()))
+            .unwrap_or_default();
+    }
+}
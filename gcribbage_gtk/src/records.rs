@@ -0,0 +1,178 @@
+use crate::callouts::capitalised;
+use crate::{COMPUTER, HUMAN};
+use gcribbage_lib::deck::Card;
+use gcribbage_lib::player::PlayerPosition;
+use gcribbage_lib::stats::{BestHand, PlayerStats, BEST_HANDS, MAX_HAND_SCORE};
+use relm4::gtk::glib::{self, KeyFile, KeyFileFlags};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The players statistics are kept for, with the group each is kept under.
+pub const PLAYERS: [(PlayerPosition, &str); 2] = [(HUMAN, "You"), (COMPUTER, "Computer")];
+
+fn path() -> PathBuf {
+    glib::user_data_dir()
+        .join("gcribbage")
+        .join("statistics.ini")
+}
+
+/// Reads a best hand written as its cards, then the up card, then the
+/// score, such as "5H 5D 5S JC 5C 29".
+fn best_hand(written: &str) -> Option<BestHand> {
+    let mut words = written.split_whitespace().collect::<Vec<&str>>();
+    let score = words.pop()?.parse().ok()?;
+    let up_card = Card::parse(words.pop()?)?;
+    let hand = words
+        .into_iter()
+        .map(Card::parse)
+        .collect::<Option<Vec<Card>>>()?;
+    Some(BestHand {
+        hand,
+        up_card,
+        score,
+    })
+}
+
+fn write_best_hand(best: &BestHand) -> String {
+    let cards = best.hand.iter().chain([&best.up_card]);
+    let mut words = cards.map(Card::notation).collect::<Vec<String>>();
+    words.push(best.score.to_string());
+    words.join(" ")
+}
+
+/// Each of the counts kept for a player, by the key it's kept under.
+fn counts(stats: &mut PlayerStats) -> [(&'static str, &mut u32); 10] {
+    [
+        ("games_won", &mut stats.games_won),
+        ("games_lost", &mut stats.games_lost),
+        ("skunks_won", &mut stats.skunks_won),
+        ("skunks_lost", &mut stats.skunks_lost),
+        ("deals", &mut stats.deals),
+        ("pegging_points", &mut stats.pegging_points),
+        ("hands", &mut stats.hands),
+        ("hand_points", &mut stats.hand_points),
+        ("cribs", &mut stats.cribs),
+        ("crib_points", &mut stats.crib_points),
+    ]
+}
+
+fn player_stats(file: &KeyFile, group: &str) -> PlayerStats {
+    let mut stats = PlayerStats::default();
+    for (key, count) in counts(&mut stats) {
+        if let Ok(value) = file.uint64(group, key) {
+            *count = value as u32;
+        }
+    }
+    if let Ok(scores) = file.string(group, "hand_scores") {
+        let scores = scores
+            .split_whitespace()
+            .map(|score| score.parse().unwrap_or(0));
+        for (tally, score) in stats.hand_scores.iter_mut().zip(scores) {
+            *tally = score;
+        }
+    }
+    stats.best_hands = (1..=BEST_HANDS)
+        .filter_map(|i| file.string(group, &format!("best_hand_{}", i)).ok())
+        .filter_map(|hand| best_hand(&hand))
+        .collect();
+    stats
+}
+
+/// The statistics saved last time, with nothing for anyone who hasn't
+/// played yet.
+pub fn load() -> HashMap<PlayerPosition, PlayerStats> {
+    let file = KeyFile::new();
+    if file.load_from_file(path(), KeyFileFlags::NONE).is_err() {
+        return HashMap::new();
+    }
+    PLAYERS
+        .iter()
+        .map(|(position, group)| (*position, player_stats(&file, group)))
+        .collect()
+}
+
+/// Saves the statistics for next time, giving back why if they couldn't be.
+pub fn save(players: &HashMap<PlayerPosition, PlayerStats>) -> Result<(), String> {
+    let file = KeyFile::new();
+    for (position, group) in PLAYERS {
+        let mut stats = players.get(&position).cloned().unwrap_or_default();
+        for (key, count) in counts(&mut stats) {
+            file.set_uint64(group, key, *count as u64);
+        }
+        let scores = stats.hand_scores.map(|score| score.to_string());
+        file.set_string(group, "hand_scores", &scores.join(" "));
+        for (i, best) in stats.best_hands.iter().enumerate() {
+            let key = format!("best_hand_{}", i + 1);
+            file.set_string(group, &key, &write_best_hand(best));
+        }
+    }
+    let path = path();
+    match path.parent().map(std::fs::create_dir_all) {
+        Some(Err(error)) => Err(error.to_string()),
+        _ => file.save_to_file(&path).map_err(|error| error.to_string()),
+    }
+}
+
+/// Writes the statistics out as a spreadsheet, one row for each player.
+pub fn export(players: &HashMap<PlayerPosition, PlayerStats>, path: &Path) -> std::io::Result<()> {
+    let mut header = vec![String::from("Player")];
+    let keys = counts(&mut PlayerStats::default()).map(|(key, _)| key);
+    header.extend(keys.map(|key| capitalised(key.replace('_', " "))));
+    header.extend((0..=MAX_HAND_SCORE).map(|score| format!("Hands scoring {}", score)));
+    let mut rows = vec![header.join(",")];
+    for (position, name) in PLAYERS {
+        let mut stats = players.get(&position).cloned().unwrap_or_default();
+        let hand_scores = stats.hand_scores;
+        let mut row = vec![String::from(name)];
+        row.extend(counts(&mut stats).map(|(_, count)| count.to_string()));
+        row.extend(hand_scores.iter().map(u32::to_string));
+        rows.push(row.join(","));
+    }
+    rows.push(String::new());
+    std::fs::write(path, rows.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_hands_are_read_back_as_written() {
+        let best = BestHand {
+            hand: ["5H", "5D", "5S", "JC"].map(Card::from).to_vec(),
+            up_card: Card::from("5C"),
+            score: 29,
+        };
+        let written = write_best_hand(&best);
+        assert_eq!("5H 5D 5S JC 5C 29", written);
+        assert_eq!(Some(best), best_hand(&written));
+        assert_eq!(
+            Some(Card::from("TD")),
+            best_hand("2C 3C 4C 6C 10D 7").map(|best| best.up_card)
+        );
+    }
+
+    #[test]
+    fn broken_best_hands_are_skipped() {
+        assert_eq!(None, best_hand(""));
+        assert_eq!(None, best_hand("29"));
+        assert_eq!(None, best_hand("5H 5D 5S JC 5C"));
+        assert_eq!(None, best_hand("5H 5D 5S XX 5C 29"));
+    }
+
+    #[test]
+    fn statistics_are_read_from_their_group() {
+        let file = KeyFile::new();
+        file.set_uint64("You", "games_won", 3);
+        file.set_uint64("Computer", "games_won", 5);
+        file.set_string("You", "hand_scores", "1 0 x 4");
+        file.set_string("You", "best_hand_1", "5H 5D 5S JC 5C 29");
+        file.set_string("You", "best_hand_2", "not a hand");
+
+        let stats = player_stats(&file, "You");
+        assert_eq!(3, stats.games_won);
+        assert_eq!([1, 0, 0, 4], stats.hand_scores[..4]);
+        assert_eq!(1, stats.best_hands.len());
+        assert_eq!(5, player_stats(&file, "Computer").games_won);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::card_names;
+use gcribbage_lib::deck::Card;
+use gcribbage_lib::strategy;
+use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+use std::collections::HashSet;
+
+/// Anything closer to the advisor's choice than this is as good as it.
+const CLOSE_ENOUGH: f64 = 0.05;
+
+/// One of the person's choices in a hand, next to what the advisor would
+/// have chosen instead.
+#[derive(Debug, Clone)]
+pub struct Verdict {
+    /// What was being chosen, such as "Discard" or "Count 12".
+    pub choice: String,
+    pub chose: String,
+    pub advised: String,
+    /// How many points the person's choice was worth less than the
+    /// advisor's, on average.
+    pub lost: f64,
+}
+
+impl Verdict {
+    /// Weighs up the cards the person put in the crib against the best
+    /// cards the hand could have given.
+    pub fn discard(hand: &HashSet<Card>, cards: &[Card], own_crib: bool) -> Option<Self> {
+        let options = strategy::discard_options(hand, cards.len(), own_crib);
+        let mut chosen = cards.to_vec();
+        chosen.sort();
+        let chose = options.iter().find(|o| o.discards == chosen)?;
+        let best = options.first()?;
+        Some(Self {
+            choice: String::from("Discard"),
+            chose: card_names(&chose.discards),
+            advised: card_names(&best.discards),
+            lost: best.value - chose.value,
+        })
+    }
+
+    /// Weighs up the card the person played onto the pile against the best
+    /// card in their hand.
+    pub fn play(hand: &HashSet<Card>, played: &[Card], card: Card) -> Option<Self> {
+        let options = strategy::play_options(hand, played);
+        let chose = options.iter().find(|o| o.card == card)?;
+        let best = options.first()?;
+        let count: u8 = played.iter().map(|c| c.rank.value()).sum();
+        Some(Self {
+            choice: match played.is_empty() {
+                true => String::from("Lead"),
+                false => format!("Count {}", count),
+            },
+            chose: chose.card.short_name(),
+            advised: best.card.short_name(),
+            lost: best.value() - chose.value(),
+        })
+    }
+
+    fn matched(&self) -> bool {
+        self.lost < CLOSE_ENOUGH
+    }
+}
+
+/// A panel going back over the person's choices in the hand just played,
+/// so they can see where they could have done better.
+pub struct Review {
+    revealed: bool,
+    verdicts: Vec<Verdict>,
+    list: gtk::ListBox,
+}
+
+#[derive(Debug)]
+pub enum ReviewMsg {
+    Show(Vec<Verdict>),
+    Hide,
+}
+
+impl Review {
+    fn summary(&self) -> String {
+        let lost = self.verdicts.iter().map(|v| v.lost).sum::<f64>();
+        match self.verdicts.iter().all(Verdict::matched) {
+            true => String::from("You made every choice the advisor would have."),
+            false => format!(
+                "Your choices were worth {:.1} points less on average than the advisor's.",
+                lost
+            ),
+        }
+    }
+
+    /// Lays out a row for each verdict.
+    fn refresh(&self) {
+        while let Some(row) = self.list.first_child() {
+            self.list.remove(&row);
+        }
+        for verdict in &self.verdicts {
+            let row = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+            let label = |text: &str, chars: i32| {
+                let label = gtk::Label::new(Some(text));
+                label.set_xalign(0.0);
+                label.set_width_chars(chars);
+                row.append(&label);
+                label
+            };
+            label(&verdict.choice, 9);
+            label(&verdict.chose, 10);
+            let (advised, lost) = match verdict.matched() {
+                true => (String::from("✓"), String::new()),
+                false => (verdict.advised.clone(), format!("−{:.1}", verdict.lost)),
+            };
+            label(&advised, 10);
+            let lost = label(&lost, 5);
+            lost.add_css_class("error");
+            self.list.append(&row);
+        }
+    }
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for Review {
+    type Init = ();
+    type Input = ReviewMsg;
+    type Output = ();
+
+    view! {
+        gtk::Revealer {
+            set_transition_type: gtk::RevealerTransitionType::SlideLeft,
+            #[watch]
+            set_reveal_child: model.revealed,
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
+                set_margin_all: 5,
+                set_width_request: 320,
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+
+                    gtk::Label {
+                        set_label: "How that hand went",
+                        set_hexpand: true,
+                        set_xalign: 0.0,
+                        add_css_class: "title-4",
+                    },
+                    gtk::Button {
+                        set_icon_name: "window-close-symbolic",
+                        set_tooltip_text: Some("Close the review"),
+                        add_css_class: "flat",
+                        connect_clicked => ReviewMsg::Hide,
+                    },
+                },
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 10,
+                    set_margin_start: 6,
+                    add_css_class: "dim-label",
+
+                    gtk::Label {
+                        set_label: "",
+                        set_width_chars: 9,
+                    },
+                    gtk::Label {
+                        set_label: "You",
+                        set_xalign: 0.0,
+                        set_width_chars: 10,
+                    },
+                    gtk::Label {
+                        set_label: "Advisor",
+                        set_xalign: 0.0,
+                        set_width_chars: 10,
+                    },
+                },
+                gtk::ScrolledWindow {
+                    set_vexpand: true,
+                    set_hscrollbar_policy: gtk::PolicyType::Never,
+
+                    #[local_ref]
+                    list -> gtk::ListBox {
+                        set_selection_mode: gtk::SelectionMode::None,
+                    },
+                },
+                gtk::Label {
+                    set_wrap: true,
+                    set_xalign: 0.0,
+                    #[watch]
+                    set_label: &model.summary(),
+                },
+            },
+        }
+    }
+
+    fn init(
+        _: Self::Init,
+        root: &Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = Review {
+            revealed: false,
+            verdicts: Vec::new(),
+            list: gtk::ListBox::new(),
+        };
+        let list = &model.list;
+        let widgets = view_output!();
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, _sender: ComponentSender<Self>) {
+        match msg {
+            ReviewMsg::Show(verdicts) => {
+                self.verdicts = verdicts;
+                self.revealed = !self.verdicts.is_empty();
+                self.refresh();
+            }
+            ReviewMsg::Hide => self.revealed = false,
+        }
+    }
+}
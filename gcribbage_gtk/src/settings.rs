@@ -0,0 +1,274 @@
+use crate::theme;
+use gcribbage_lib::rules::GameRules;
+use gcribbage_lib::strategy::Difficulty;
+use relm4::gtk::glib::{self, KeyFile, KeyFileFlags};
+use std::path::PathBuf;
+
+/// The group in the settings file everything is kept under.
+const GROUP: &str = "Preferences";
+
+/// The scores a game can be played to.
+pub const TARGET_SCORES: [u8; 2] = [121, 61];
+
+/// How many cards each player is dealt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    SixCard,
+    /// Five cards each, with the pone getting three points to start to
+    /// make up for the dealer's crib.
+    FiveCard,
+}
+
+impl Variant {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Variant::SixCard, Variant::FiveCard].into_iter()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variant::SixCard => "six-card",
+            Variant::FiveCard => "five-card",
+        }
+    }
+}
+
+/// How quickly things move around the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationSpeed {
+    Off,
+    Fast,
+    Normal,
+    Slow,
+}
+
+impl AnimationSpeed {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        use AnimationSpeed::*;
+        [Off, Fast, Normal, Slow].into_iter()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnimationSpeed::Off => "off",
+            AnimationSpeed::Fast => "fast",
+            AnimationSpeed::Normal => "normal",
+            AnimationSpeed::Slow => "slow",
+        }
+    }
+
+    /// How long animations take, as a multiple of their usual time.
+    pub fn time(&self) -> f64 {
+        match self {
+            AnimationSpeed::Off => 0.0,
+            AnimationSpeed::Fast => 0.5,
+            AnimationSpeed::Normal => 1.0,
+            AnimationSpeed::Slow => 2.0,
+        }
+    }
+}
+
+/// Everything the person can choose about how the app plays and looks.
+/// These are kept in the user's config directory between runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// How well the computer plays.
+    pub difficulty: Difficulty,
+    pub variant: Variant,
+    pub target_score: u8,
+    /// Whether a four-card flush in the crib scores.
+    pub crib_four_card_flush: bool,
+    /// The name of the deck the cards are drawn from.
+    pub theme: String,
+    /// Whether to draw the cards in high contrast, with each suit in its
+    /// own colour.
+    pub four_colour: bool,
+    pub animation: AnimationSpeed,
+    /// Whether hands are counted without stopping to show how.
+    pub auto_count: bool,
+    /// Whether the person can ask for hints.
+    pub advisor: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            difficulty: Difficulty::Medium,
+            variant: Variant::SixCard,
+            target_score: TARGET_SCORES[0],
+            crib_four_card_flush: false,
+            theme: String::from(theme::BUILTIN),
+            four_colour: false,
+            animation: AnimationSpeed::Normal,
+            auto_count: false,
+            advisor: true,
+        }
+    }
+}
+
+fn path() -> PathBuf {
+    glib::user_config_dir()
+        .join("gcribbage")
+        .join("settings.ini")
+}
+
+impl Settings {
+    /// The settings saved last time, with anything missing or that can't be
+    /// read left as it is by default.
+    pub fn load() -> Self {
+        let file = KeyFile::new();
+        match file.load_from_file(path(), KeyFileFlags::NONE) {
+            Ok(_) => Self::read(&file),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Reads the settings kept in a settings file.
+    fn read(file: &KeyFile) -> Self {
+        let mut settings = Self::default();
+        let string = |key| file.string(GROUP, key).ok();
+        let boolean = |key| file.boolean(GROUP, key).ok();
+        if let Some(difficulty) = string("difficulty").and_then(|d| Difficulty::from_name(&d)) {
+            settings.difficulty = difficulty;
+        }
+        if let Some(variant) =
+            string("variant").and_then(|v| Variant::iter().find(|o| o.name() == v.as_str()))
+        {
+            settings.variant = variant;
+        }
+        if let Some(target) = file
+            .integer(GROUP, "target_score")
+            .ok()
+            .and_then(|t| TARGET_SCORES.into_iter().find(|score| *score as i32 == t))
+        {
+            settings.target_score = target;
+        }
+        if let Some(theme) = string("theme") {
+            settings.theme = theme.to_string();
+        }
+        if let Some(speed) = string("animation")
+            .and_then(|a| AnimationSpeed::iter().find(|o| o.name() == a.as_str()))
+        {
+            settings.animation = speed;
+        }
+        let flags = [
+            ("crib_four_card_flush", &mut settings.crib_four_card_flush),
+            ("four_colour", &mut settings.four_colour),
+            ("auto_count", &mut settings.auto_count),
+            ("advisor", &mut settings.advisor),
+        ];
+        for (key, flag) in flags {
+            if let Some(value) = boolean(key) {
+                *flag = value;
+            }
+        }
+        settings
+    }
+
+    /// Saves the settings for next time, giving back why if they couldn't
+    /// be.
+    pub fn save(&self) -> Result<(), String> {
+        let file = self.write();
+        let path = path();
+        match path.parent().map(std::fs::create_dir_all) {
+            Some(Err(error)) => Err(error.to_string()),
+            _ => file.save_to_file(&path).map_err(|error| error.to_string()),
+        }
+    }
+
+    /// Writes the settings out as they're kept in a settings file.
+    fn write(&self) -> KeyFile {
+        let file = KeyFile::new();
+        file.set_string(GROUP, "difficulty", self.difficulty.name());
+        file.set_string(GROUP, "variant", self.variant.name());
+        file.set_integer(GROUP, "target_score", self.target_score as i32);
+        file.set_boolean(GROUP, "crib_four_card_flush", self.crib_four_card_flush);
+        file.set_string(GROUP, "theme", &self.theme);
+        file.set_boolean(GROUP, "four_colour", self.four_colour);
+        file.set_string(GROUP, "animation", self.animation.name());
+        file.set_boolean(GROUP, "auto_count", self.auto_count);
+        file.set_boolean(GROUP, "advisor", self.advisor);
+        file
+    }
+
+    /// The rules to play the next game by.
+    pub fn rules(&self) -> GameRules {
+        let rules = match self.variant {
+            Variant::SixCard => GameRules::new(),
+            Variant::FiveCard => GameRules::five_card(),
+        };
+        // Five-card is usually played to 61, but either can be played to
+        // either score, and the skunk lines go with the score.
+        let length = match self.target_score {
+            61 => GameRules::short_game(),
+            _ => GameRules::new(),
+        };
+        GameRules {
+            target_score: length.target_score,
+            skunk_line: length.skunk_line,
+            double_skunk_line: length.double_skunk_line,
+            crib_four_card_flush: self.crib_four_card_flush,
+            ..rules
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_follow_the_variant_and_score() {
+        let rules = |variant, target_score| {
+            let settings = Settings {
+                variant,
+                target_score,
+                ..Settings::default()
+            };
+            settings.rules()
+        };
+        assert_eq!(GameRules::new(), rules(Variant::SixCard, 121));
+        assert_eq!(GameRules::short_game(), rules(Variant::SixCard, 61));
+        assert_eq!(GameRules::five_card(), rules(Variant::FiveCard, 61));
+
+        let long_five_card = rules(Variant::FiveCard, 121);
+        assert_eq!(5, long_five_card.deal_size);
+        assert_eq!(3, long_five_card.pone_head_start);
+        assert_eq!(121, long_five_card.target_score);
+        assert_eq!(GameRules::new().skunk_line, long_five_card.skunk_line);
+
+        let settings = Settings {
+            crib_four_card_flush: true,
+            ..Settings::default()
+        };
+        assert!(settings.rules().crib_four_card_flush);
+    }
+
+    #[test]
+    fn settings_are_read_back_as_written() {
+        let settings = Settings {
+            difficulty: Difficulty::Hard,
+            variant: Variant::FiveCard,
+            target_score: 61,
+            crib_four_card_flush: true,
+            theme: String::from("Bonded"),
+            four_colour: true,
+            animation: AnimationSpeed::Slow,
+            auto_count: true,
+            advisor: false,
+        };
+        assert_eq!(settings, Settings::read(&settings.write()));
+        assert_eq!(
+            Settings::default(),
+            Settings::read(&Settings::default().write())
+        );
+    }
+
+    #[test]
+    fn unreadable_settings_are_left_as_they_are() {
+        let file = KeyFile::new();
+        file.set_string(GROUP, "difficulty", "impossible");
+        file.set_integer(GROUP, "target_score", 100);
+        file.set_string(GROUP, "advisor", "perhaps");
+        assert_eq!(Settings::default(), Settings::read(&file));
+    }
+}
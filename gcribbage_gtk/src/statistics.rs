@@ -0,0 +1,375 @@
+use crate::records::{self, PLAYERS};
+use crate::widgets::{CardBox, TableCard};
+use gcribbage_lib::player::PlayerPosition;
+use gcribbage_lib::stats::{PlayerStats, BEST_HANDS, MAX_HAND_SCORE};
+use relm4::gtk::glib::BoxedAnyObject;
+use relm4::gtk::prelude::*;
+use relm4::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// One of the best hands, as it's laid out in the window.
+struct BestHandRow {
+    row: gtk::Box,
+    score: gtk::Label,
+    hand: CardBox,
+    up_card: CardBox,
+}
+
+/// A window showing how each player has done over every game they've
+/// played.
+pub struct StatisticsView {
+    window: gtk::Window,
+    hidden: bool,
+    players: HashMap<PlayerPosition, PlayerStats>,
+    /// Which of the players is being shown, by where they are in
+    /// `PLAYERS`.
+    shown: usize,
+    /// What happened when the statistics were last exported.
+    message: String,
+    /// A bar for each hand score, lowest first.
+    histogram: Vec<gtk::LevelBar>,
+    best_hands: Vec<BestHandRow>,
+    export_dialog: Option<gtk::FileChooserNative>,
+}
+
+#[derive(Debug)]
+pub enum StatisticsMsg {
+    Show(HashMap<PlayerPosition, PlayerStats>),
+    Hide,
+    PlayerSelected(u32),
+    Export,
+    ExportTo(PathBuf),
+    Reset,
+    SetTheme(String),
+    SetFourColour(bool),
+}
+
+#[derive(Debug)]
+pub enum StatisticsOutput {
+    /// The person has asked for all the statistics to be thrown away.
+    Reset,
+}
+
+impl StatisticsView {
+    fn stats(&self) -> PlayerStats {
+        let position = PLAYERS[self.shown].0;
+        self.players.get(&position).cloned().unwrap_or_default()
+    }
+
+    fn record(&self) -> String {
+        let stats = self.stats();
+        let rate = match stats.games() {
+            0 => 0.0,
+            games => stats.games_won as f64 * 100.0 / games as f64,
+        };
+        format!(
+            "{} won, {} lost ({:.0}%)",
+            stats.games_won, stats.games_lost, rate
+        )
+    }
+
+    fn skunks(&self) -> String {
+        let stats = self.stats();
+        format!("{} given, {} taken", stats.skunks_won, stats.skunks_lost)
+    }
+
+    /// Puts the chosen player's hand scores and best hands into the widgets
+    /// made for them.
+    fn refresh(&self) {
+        let stats = self.stats();
+        let most = stats.hand_scores.iter().max().copied().unwrap_or(0).max(1);
+        for (score, (bar, hands)) in self.histogram.iter().zip(stats.hand_scores).enumerate() {
+            bar.set_max_value(most as f64);
+            bar.set_value(hands as f64);
+            let tip = format!("{} hands scored {}", hands, score);
+            bar.set_tooltip_text(Some(&tip));
+            bar.update_property(&[gtk::accessible::Property::Label(&tip)]);
+        }
+        for (i, row) in self.best_hands.iter().enumerate() {
+            match stats.best_hands.get(i) {
+                Some(best) => {
+                    row.score.set_label(&best.score.to_string());
+                    row.hand
+                        .set_hand(BoxedAnyObject::new(TableCard::face_up(&best.hand)));
+                    row.up_card
+                        .set_hand(BoxedAnyObject::new(vec![TableCard::FaceUp(best.up_card)]));
+                    row.row.set_visible(true);
+                }
+                None => row.row.set_visible(false),
+            }
+        }
+    }
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for StatisticsView {
+    /// The name of the deck the cards are drawn from.
+    type Init = String;
+    type Input = StatisticsMsg;
+    type Output = StatisticsOutput;
+
+    view! {
+        gtk::Window {
+            set_title: Some("Statistics"),
+            set_modal: true,
+            set_hide_on_close: true,
+            set_default_size: (600, 640),
+            #[watch]
+            set_visible: !model.hidden,
+            connect_close_request[sender] => move |_| {
+                sender.input(StatisticsMsg::Hide);
+                gtk::Inhibit(true)
+            },
+
+            gtk::ScrolledWindow {
+                set_hscrollbar_policy: gtk::PolicyType::Never,
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 10,
+                    set_margin_all: 10,
+
+                    gtk::DropDown::from_strings(&["You", "The computer"]) {
+                        set_halign: gtk::Align::Start,
+                        connect_selected_notify[sender] => move |choice| {
+                            sender.input(StatisticsMsg::PlayerSelected(choice.selected()));
+                        },
+                    },
+                    gtk::Grid {
+                        set_row_spacing: 5,
+                        set_column_spacing: 10,
+
+                        attach[0, 0, 1, 1] = &gtk::Label {
+                            set_label: "Games",
+                            set_halign: gtk::Align::Start,
+                        },
+                        attach[1, 0, 1, 1] = &gtk::Label {
+                            set_halign: gtk::Align::Start,
+                            #[watch]
+                            set_label: &model.record(),
+                        },
+                        attach[0, 1, 1, 1] = &gtk::Label {
+                            set_label: "Skunks",
+                            set_halign: gtk::Align::Start,
+                        },
+                        attach[1, 1, 1, 1] = &gtk::Label {
+                            set_halign: gtk::Align::Start,
+                            #[watch]
+                            set_label: &model.skunks(),
+                        },
+                        attach[0, 2, 1, 1] = &gtk::Label {
+                            set_label: "Average hand",
+                            set_halign: gtk::Align::Start,
+                        },
+                        attach[1, 2, 1, 1] = &gtk::Label {
+                            set_halign: gtk::Align::Start,
+                            #[watch]
+                            set_label: &format!("{:.1}", model.stats().average_hand()),
+                        },
+                        attach[0, 3, 1, 1] = &gtk::Label {
+                            set_label: "Average crib",
+                            set_halign: gtk::Align::Start,
+                        },
+                        attach[1, 3, 1, 1] = &gtk::Label {
+                            set_halign: gtk::Align::Start,
+                            #[watch]
+                            set_label: &format!("{:.1}", model.stats().average_crib()),
+                        },
+                        attach[0, 4, 1, 1] = &gtk::Label {
+                            set_label: "Average pegged a deal",
+                            set_halign: gtk::Align::Start,
+                        },
+                        attach[1, 4, 1, 1] = &gtk::Label {
+                            set_halign: gtk::Align::Start,
+                            #[watch]
+                            set_label: &format!("{:.1}", model.stats().average_pegging()),
+                        },
+                    },
+                    gtk::Label {
+                        set_label: "Hand scores",
+                        set_halign: gtk::Align::Start,
+                        add_css_class: "title-4",
+                    },
+                    #[name = "histogram"]
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 2,
+                        set_homogeneous: true,
+                        set_height_request: 140,
+                    },
+                    gtk::Label {
+                        set_label: "Best hands",
+                        set_halign: gtk::Align::Start,
+                        add_css_class: "title-4",
+                    },
+                    #[name = "best_hands"]
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 5,
+                    },
+                    gtk::Label {
+                        add_css_class: "dim-label",
+                        #[watch]
+                        set_label: &model.message,
+                        #[watch]
+                        set_visible: !model.message.is_empty(),
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 5,
+                        set_halign: gtk::Align::End,
+
+                        gtk::Button {
+                            set_label: "Export…",
+                            set_tooltip_text: Some("Save the statistics as a spreadsheet"),
+                            connect_clicked => StatisticsMsg::Export,
+                        },
+                        gtk::Button {
+                            set_label: "Reset",
+                            add_css_class: "destructive-action",
+                            set_tooltip_text: Some("Forget every game played so far"),
+                            connect_clicked => StatisticsMsg::Reset,
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        theme: Self::Init,
+        root: &Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let mut model = StatisticsView {
+            window: root.clone(),
+            hidden: true,
+            players: HashMap::new(),
+            shown: 0,
+            message: String::new(),
+            histogram: Vec::new(),
+            best_hands: Vec::new(),
+            export_dialog: None,
+        };
+        let widgets = view_output!();
+
+        for score in 0..=MAX_HAND_SCORE {
+            let bar = gtk::LevelBar::builder()
+                .orientation(gtk::Orientation::Vertical)
+                .inverted(true)
+                .vexpand(true)
+                .build();
+            // Level bars change colour as they fill, which means nothing
+            // here.
+            bar.remove_offset_value(Some(gtk::LEVEL_BAR_OFFSET_LOW));
+            bar.remove_offset_value(Some(gtk::LEVEL_BAR_OFFSET_HIGH));
+            bar.remove_offset_value(Some(gtk::LEVEL_BAR_OFFSET_FULL));
+            let column = gtk::Box::new(gtk::Orientation::Vertical, 2);
+            column.append(&bar);
+            let label = gtk::Label::new(Some(&score.to_string()));
+            label.add_css_class("caption");
+            column.append(&label);
+            widgets.histogram.append(&column);
+            model.histogram.push(bar);
+        }
+        for _ in 0..BEST_HANDS {
+            let card_box = |label: &str| {
+                let card_box = CardBox::new();
+                card_box.set_theme(theme.as_str());
+                card_box.set_label(label);
+                card_box.set_height_request(90);
+                card_box
+            };
+            let row = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+            let score = gtk::Label::new(None);
+            score.add_css_class("title-2");
+            score.set_width_chars(3);
+            let hand = card_box("Hand");
+            hand.set_hexpand(true);
+            let up_card = card_box("Up card");
+            up_card.set_width_request(80);
+            row.append(&score);
+            row.append(&hand);
+            row.append(&up_card);
+            widgets.best_hands.append(&row);
+            model.best_hands.push(BestHandRow {
+                row,
+                score,
+                hand,
+                up_card,
+            });
+        }
+        model.refresh();
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            StatisticsMsg::Show(players) => {
+                self.players = players;
+                self.message.clear();
+                self.hidden = false;
+            }
+            StatisticsMsg::Hide => self.hidden = true,
+            StatisticsMsg::PlayerSelected(i) => self.shown = (i as usize).min(PLAYERS.len() - 1),
+            StatisticsMsg::Export => {
+                let dialog = gtk::FileChooserNative::new(
+                    Some("Export statistics"),
+                    Some(&self.window),
+                    gtk::FileChooserAction::Save,
+                    Some("Export"),
+                    None,
+                );
+                dialog.set_current_name("cribbage-statistics.csv");
+                let sender = sender.clone();
+                dialog.connect_response(move |dialog, response| {
+                    let path = dialog.file().and_then(|file| file.path());
+                    if let (gtk::ResponseType::Accept, Some(path)) = (response, path) {
+                        sender.input(StatisticsMsg::ExportTo(path));
+                    }
+                });
+                dialog.show();
+                self.export_dialog = Some(dialog);
+            }
+            StatisticsMsg::ExportTo(path) => {
+                self.message = match records::export(&self.players, &path) {
+                    Ok(()) => format!("Exported to {}.", path.display()),
+                    Err(error) => format!("Could not export the statistics: {}", error),
+                };
+            }
+            StatisticsMsg::Reset => {
+                let dialog = gtk::MessageDialog::builder()
+                    .transient_for(&self.window)
+                    .modal(true)
+                    .message_type(gtk::MessageType::Question)
+                    .buttons(gtk::ButtonsType::OkCancel)
+                    .text("Reset the statistics?")
+                    .secondary_text("Every game played so far will be forgotten.")
+                    .build();
+                let sender = sender.clone();
+                dialog.connect_response(move |dialog, response| {
+                    if response == gtk::ResponseType::Ok {
+                        sender.output(StatisticsOutput::Reset).unwrap_or_default();
+                        sender.input(StatisticsMsg::Show(HashMap::new()));
+                    }
+                    dialog.close();
+                });
+                dialog.show();
+            }
+            StatisticsMsg::SetTheme(name) => {
+                for row in &self.best_hands {
+                    row.hand.set_theme(name.as_str());
+                    row.up_card.set_theme(name.as_str());
+                }
+            }
+            StatisticsMsg::SetFourColour(four_colour) => {
+                for row in &self.best_hands {
+                    row.hand.set_four_colour(four_colour);
+                    row.up_card.set_four_colour(four_colour);
+                }
+            }
+        }
+        self.refresh();
+    }
+}
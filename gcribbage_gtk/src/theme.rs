@@ -0,0 +1,726 @@
+use gcribbage_lib::deck::{Card, Rank, Suit};
+use relm4::gtk::{
+    gdk_pixbuf::{Colorspace, Pixbuf},
+    gio, glib,
+};
+use roxmltree::{Document, Node, ParsingOptions};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+
+/// The name of the deck that's built in to the app.
+pub const BUILTIN: &str = "Anglo";
+const BUILTIN_RESOURCE: &str = "/com/ronsbrain/gcribbage_gtk/anglo.svg";
+
+/// Where a deck's SVG comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    Resource(String),
+    File(PathBuf),
+}
+
+/// A deck of cards drawn in an SVG, laid out the way AisleRiot decks are.
+///
+/// AisleRiot decks can be laid out however their artists like, but each
+/// card is an element with an id such as `club_1` or `heart_queen`, and the
+/// card back is `back`. Each of those starts with the outline of the card,
+/// so that's what is used to find where the card is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeckTheme {
+    pub name: String,
+    source: Source,
+}
+
+#[derive(Debug)]
+pub enum ThemeError {
+    Unreadable(String),
+    /// The SVG has no card with this id, or it couldn't be found.
+    MissingCard(String),
+    Render(glib::Error),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThemeError::Unreadable(why) => write!(f, "Could not read the deck: {}", why),
+            ThemeError::MissingCard(id) => write!(f, "The deck has no card called {}", id),
+            ThemeError::Render(error) => write!(f, "Could not draw the deck: {}", error),
+        }
+    }
+}
+
+/// The cards of a deck, drawn at a particular size.
+pub struct DeckImages {
+    pub cards: HashMap<Card, Pixbuf>,
+    pub back: Pixbuf,
+}
+
+impl DeckTheme {
+    pub fn builtin() -> Self {
+        Self {
+            name: String::from(BUILTIN),
+            source: Source::Resource(String::from(BUILTIN_RESOURCE)),
+        }
+    }
+
+    fn from_file(path: &Path) -> Option<Self> {
+        if path.extension()? != "svg" {
+            return None;
+        }
+        Some(Self {
+            name: path.file_stem()?.to_string_lossy().into_owned(),
+            source: Source::File(path.to_path_buf()),
+        })
+    }
+
+    /// Every deck that can be used. That's the built in one, then any SVG
+    /// decks in `gcribbage/decks` in the user's data directory, then any
+    /// AisleRiot has installed.
+    pub fn available() -> Vec<Self> {
+        let mut dirs = vec![glib::user_data_dir().join("gcribbage").join("decks")];
+        dirs.extend(
+            glib::system_data_dirs()
+                .iter()
+                .map(|dir| dir.join("aisleriot").join("cards")),
+        );
+
+        let mut themes = vec![Self::builtin()];
+        for dir in dirs {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            let mut found = entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| Self::from_file(&entry.path()))
+                .filter(|theme| themes.iter().all(|t: &Self| t.name != theme.name))
+                .collect::<Vec<Self>>();
+            found.sort_by(|a, b| a.name.cmp(&b.name));
+            themes.extend(found);
+        }
+        themes
+    }
+
+    /// The deck with the given name, or the built in one if there's no such
+    /// deck.
+    pub fn named(name: &str) -> Self {
+        Self::available()
+            .into_iter()
+            .find(|theme| theme.name == name)
+            .unwrap_or_else(Self::builtin)
+    }
+
+    fn read(&self) -> Result<String, ThemeError> {
+        match &self.source {
+            Source::Resource(path) => {
+                let bytes = gio::resources_lookup_data(path, gio::ResourceLookupFlags::NONE)
+                    .map_err(|e| ThemeError::Unreadable(e.to_string()))?;
+                String::from_utf8(bytes.to_vec()).map_err(|e| ThemeError::Unreadable(e.to_string()))
+            }
+            Source::File(path) => {
+                std::fs::read_to_string(path).map_err(|e| ThemeError::Unreadable(e.to_string()))
+            }
+        }
+    }
+
+    fn render(&self, width: i32, height: i32) -> Result<Pixbuf, ThemeError> {
+        match &self.source {
+            Source::Resource(path) => Pixbuf::from_resource_at_scale(path, width, height, false),
+            Source::File(path) => Pixbuf::from_file_at_scale(path, width, height, false),
+        }
+        .map_err(ThemeError::Render)
+    }
+
+    /// Finds where each card is in the deck's SVG.
+    fn layout(&self) -> Result<DeckLayout, ThemeError> {
+        DeckLayout::read(&self.read()?)
+    }
+
+    /// Draws the whole deck with each card the given number of pixels tall,
+    /// in high contrast four colour suits if asked for.
+    fn draw(
+        &self,
+        layout: &DeckLayout,
+        card_height: i32,
+        four_colour: bool,
+    ) -> Result<DeckImages, ThemeError> {
+        let scale = card_height as f64 / layout.back.height;
+        let drawn = self.render(
+            (layout.width * scale).round() as i32,
+            (layout.height * scale).round() as i32,
+        )?;
+        let cut = |rect: &Rect| {
+            let x = ((rect.x * scale).round() as i32).clamp(0, drawn.width() - 1);
+            let y = ((rect.y * scale).round() as i32).clamp(0, drawn.height() - 1);
+            let width = ((rect.width * scale).round() as i32).clamp(1, drawn.width() - x);
+            let height = ((rect.height * scale).round() as i32).clamp(1, drawn.height() - y);
+            drawn.new_subpixbuf(x, y, width, height)
+        };
+        Ok(DeckImages {
+            cards: layout
+                .cards
+                .iter()
+                .map(|(card, rect)| match four_colour {
+                    true => (*card, recolour(card.suit, &cut(rect))),
+                    false => (*card, cut(rect)),
+                })
+                .collect(),
+            back: cut(&layout.back),
+        })
+    }
+}
+
+/// Where the cards are in a deck's SVG, once its size is worked out.
+struct DeckLayout {
+    width: f64,
+    height: f64,
+    cards: HashMap<Card, Rect>,
+    back: Rect,
+}
+
+impl DeckLayout {
+    fn read(svg: &str) -> Result<Self, ThemeError> {
+        let options = ParsingOptions {
+            allow_dtd: true,
+            ..ParsingOptions::default()
+        };
+        let svg = Document::parse_with_options(svg, options)
+            .map_err(|e| ThemeError::Unreadable(e.to_string()))?;
+        let page = Page::new(svg.root_element())
+            .ok_or_else(|| ThemeError::Unreadable(String::from("it doesn't say how big it is")))?;
+        let bounds = |id: &str| {
+            element(&svg, id)
+                .and_then(extent)
+                .map(|rect| page.place(rect))
+                .ok_or_else(|| ThemeError::MissingCard(String::from(id)))
+        };
+
+        let mut cards = HashMap::new();
+        for suit in Suit::iter() {
+            for rank in Rank::iter() {
+                let card = Card::new(suit, rank);
+                cards.insert(card, bounds(&card_id(card))?);
+            }
+        }
+        Ok(Self {
+            width: page.width,
+            height: page.height,
+            cards,
+            back: bounds("back")?,
+        })
+    }
+}
+
+/// The decks that have been read, and the card images drawn from them.
+///
+/// Drawing a deck means rendering its whole SVG, which is slow, so every
+/// widget showing cards of the same size from the same deck shares one set
+/// of images. The cache only keeps weak references to the images, so a
+/// size nobody is showing any more is dropped along with the last widget
+/// that used it.
+#[derive(Default)]
+struct CardCache {
+    layouts: HashMap<String, Rc<(DeckTheme, DeckLayout)>>,
+    /// Images by deck, height and whether they're in four colours.
+    images: HashMap<(String, i32, bool), Weak<DeckImages>>,
+}
+
+thread_local! {
+    static CACHE: RefCell<CardCache> = RefCell::default();
+}
+
+impl CardCache {
+    /// The named deck and where its cards are. If that deck can't be read,
+    /// the built in one stands in for it.
+    fn layout(&mut self, name: &str) -> Rc<(DeckTheme, DeckLayout)> {
+        let layout = self.layouts.entry(String::from(name)).or_insert_with(|| {
+            let theme = DeckTheme::named(name);
+            let layout = theme.layout().unwrap_or_else(|error| {
+                glib::g_warning!(crate::LOG_DOMAIN, "{}", error);
+                DeckTheme::builtin()
+                    .layout()
+                    .expect("Could not read the built in deck")
+            });
+            let theme = match theme.name == name {
+                true => theme,
+                false => DeckTheme::builtin(),
+            };
+            Rc::new((theme, layout))
+        });
+        Rc::clone(layout)
+    }
+
+    fn images(&mut self, name: &str, card_height: i32, four_colour: bool) -> Rc<DeckImages> {
+        self.images.retain(|_, images| images.strong_count() > 0);
+        let key = (String::from(name), card_height, four_colour);
+        if let Some(images) = self.images.get(&key).and_then(Weak::upgrade) {
+            return images;
+        }
+        let layout = self.layout(name);
+        let (theme, layout) = layout.as_ref();
+        let images = theme
+            .draw(layout, card_height, four_colour)
+            .unwrap_or_else(|error| {
+                glib::g_warning!(crate::LOG_DOMAIN, "{}", error);
+                let builtin = self.layout(BUILTIN);
+                builtin
+                    .0
+                    .draw(&builtin.1, card_height, four_colour)
+                    .expect("Could not draw the built in deck")
+            });
+        let images = Rc::new(images);
+        self.images.insert(key, Rc::downgrade(&images));
+        images
+    }
+}
+
+/// The cards from the named deck, drawn the given number of pixels tall.
+/// They're only drawn if nothing else is already showing them at that size.
+pub fn card_images(name: &str, card_height: i32, four_colour: bool) -> Rc<DeckImages> {
+    CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .images(name, card_height.max(1), four_colour)
+    })
+}
+
+/// Redraws a card with its suit in a colour of its own and everything a
+/// little starker. Diamonds turn from red to blue, and clubs from black to
+/// green, so no two suits look alike.
+fn recolour(suit: Suit, pixbuf: &Pixbuf) -> Pixbuf {
+    let mut pixels = pixbuf.read_pixel_bytes().to_vec();
+    let channels = pixbuf.n_channels() as usize;
+    let row = pixbuf.width() as usize * channels;
+    for line in pixels.chunks_mut(pixbuf.rowstride() as usize) {
+        for pixel in line[..row].chunks_exact_mut(channels) {
+            let [r, g, b] = contrast([pixel[0], pixel[1], pixel[2]]);
+            let brightest = r.max(g).max(b);
+            let darkest = r.min(g).min(b);
+            let [r, g, b] = match suit {
+                // Red ink is swapped for blue ink.
+                Suit::Diamonds if r as i32 - g.max(b) as i32 > 60 => [b, g, r],
+                // Black ink, and the greys at its edges, is mixed with
+                // green instead of black.
+                Suit::Clubs if brightest - darkest < 30 => {
+                    let green = 140 * (255 - brightest as u32) / 255;
+                    [brightest, brightest + green as u8, brightest]
+                }
+                _ => [r, g, b],
+            };
+            pixel[..3].copy_from_slice(&[r, g, b]);
+        }
+    }
+    Pixbuf::from_mut_slice(
+        pixels,
+        Colorspace::Rgb,
+        pixbuf.has_alpha(),
+        pixbuf.bits_per_sample(),
+        pixbuf.width(),
+        pixbuf.height(),
+        pixbuf.rowstride(),
+    )
+}
+
+/// Pushes each channel of a colour away from the middle, so light things
+/// are lighter and dark things darker.
+fn contrast(colour: [u8; 3]) -> [u8; 3] {
+    colour.map(|c| ((c as f64 - 128.0) * 1.3 + 128.0).clamp(0.0, 255.0) as u8)
+}
+
+/// The id AisleRiot gives the card in its decks.
+fn card_id(card: Card) -> String {
+    let suit = match card.suit {
+        Suit::Clubs => "club",
+        Suit::Diamonds => "diamond",
+        Suit::Hearts => "heart",
+        Suit::Spades => "spade",
+    };
+    let rank = match card.rank {
+        Rank::Jack => String::from("jack"),
+        Rank::Queen => String::from("queen"),
+        Rank::King => String::from("king"),
+        rank => rank.ordinal().to_string(),
+    };
+    format!("{}_{}", suit, rank)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// An SVG transform, as the matrix `[a c e; b d f; 0 0 1]`.
+#[derive(Debug, Clone, Copy)]
+struct Transform([f64; 6]);
+
+impl Transform {
+    const IDENTITY: Self = Self([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    fn translate(x: f64, y: f64) -> Self {
+        Self([1.0, 0.0, 0.0, 1.0, x, y])
+    }
+
+    /// The transform that does `other` first, then this one.
+    fn compose(&self, other: &Self) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let [g, h, i, j, k, l] = other.0;
+        Self([
+            a * g + c * h,
+            b * g + d * h,
+            a * i + c * j,
+            b * i + d * j,
+            a * k + c * l + e,
+            b * k + d * l + f,
+        ])
+    }
+
+    fn apply(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let [a, b, c, d, e, f] = self.0;
+        (a * x + c * y + e, b * x + d * y + f)
+    }
+
+    /// The box around the rectangle once it's been transformed.
+    fn bound(&self, rect: Rect) -> Rect {
+        let corners = [
+            (rect.x, rect.y),
+            (rect.x + rect.width, rect.y),
+            (rect.x, rect.y + rect.height),
+            (rect.x + rect.width, rect.y + rect.height),
+        ]
+        .map(|corner| self.apply(corner));
+        let xs = corners.map(|(x, _)| x);
+        let ys = corners.map(|(_, y)| y);
+        let min = |v: [f64; 4]| v.into_iter().fold(f64::INFINITY, f64::min);
+        let max = |v: [f64; 4]| v.into_iter().fold(f64::NEG_INFINITY, f64::max);
+        Rect {
+            x: min(xs),
+            y: min(ys),
+            width: max(xs) - min(xs),
+            height: max(ys) - min(ys),
+        }
+    }
+
+    /// Reads a `transform` attribute. Anything that can't be made sense of
+    /// is left out.
+    fn parse(text: &str) -> Self {
+        let mut transform = Self::IDENTITY;
+        for part in text.split(')') {
+            let Some((name, args)) = part.split_once('(') else {
+                continue;
+            };
+            let args = numbers(args);
+            let arg = |i: usize| args.get(i).copied().unwrap_or(0.0);
+            let next = match (
+                name.trim_matches(|c: char| c.is_whitespace() || c == ','),
+                args.len(),
+            ) {
+                ("matrix", 6) => Self([arg(0), arg(1), arg(2), arg(3), arg(4), arg(5)]),
+                ("translate", _) => Self::translate(arg(0), arg(1)),
+                ("scale", 1) => Self([arg(0), 0.0, 0.0, arg(0), 0.0, 0.0]),
+                ("scale", _) => Self([arg(0), 0.0, 0.0, arg(1), 0.0, 0.0]),
+                ("rotate", _) => {
+                    let (sin, cos) = arg(0).to_radians().sin_cos();
+                    let rotate = Self([cos, sin, -sin, cos, 0.0, 0.0]);
+                    Self::translate(arg(1), arg(2))
+                        .compose(&rotate)
+                        .compose(&Self::translate(-arg(1), -arg(2)))
+                }
+                _ => continue,
+            };
+            transform = transform.compose(&next);
+        }
+        transform
+    }
+}
+
+fn numbers(text: &str) -> Vec<f64> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(|n| n.parse().ok())
+        .collect()
+}
+
+/// The namespace older SVGs put `href` in.
+const XLINK: &str = "http://www.w3.org/1999/xlink";
+
+/// Reads a length as a number of pixels, taking an inch to be 96 pixels as
+/// SVG does. Lengths relative to something else, such as percentages, can't
+/// be worked out.
+fn length(text: &str) -> Option<f64> {
+    let units = [
+        ("px", 1.0),
+        ("pt", 96.0 / 72.0),
+        ("pc", 16.0),
+        ("mm", 96.0 / 25.4),
+        ("cm", 96.0 / 2.54),
+        ("in", 96.0),
+    ];
+    let text = text.trim();
+    let (number, scale) = units
+        .iter()
+        .find_map(|(unit, scale)| Some((text.strip_suffix(unit)?, *scale)))
+        .unwrap_or((text, 1.0));
+    number.trim_end().parse::<f64>().ok().map(|n| n * scale)
+}
+
+fn number(node: Node, name: &str) -> f64 {
+    node.attribute(name).and_then(length).unwrap_or(0.0)
+}
+
+fn transform(node: Node) -> Transform {
+    node.attribute("transform")
+        .map_or(Transform::IDENTITY, Transform::parse)
+}
+
+/// Finds the element with the given id.
+fn element<'a, 'input>(svg: &'a Document<'input>, id: &str) -> Option<Node<'a, 'input>> {
+    svg.descendants()
+        .find(|node| node.attribute("id") == Some(id))
+}
+
+/// Works out the outline of an element, in the coordinates of whatever it's
+/// in. That's the rectangle itself for a `rect`, the outline of whatever a
+/// `use` points at, and the outline of the first of those inside anything
+/// else.
+fn outline(node: Node, depth: usize) -> Option<Rect> {
+    // References can go round in circles in a broken SVG.
+    if depth > 8 {
+        return None;
+    }
+    let rect = match node.tag_name().name() {
+        "rect" => Rect {
+            x: number(node, "x"),
+            y: number(node, "y"),
+            width: number(node, "width"),
+            height: number(node, "height"),
+        },
+        "use" => {
+            let href = node
+                .attribute((XLINK, "href"))
+                .or_else(|| node.attribute("href"))?;
+            let target = element(node.document(), href.strip_prefix('#')?)?;
+            let offset = Transform::translate(number(node, "x"), number(node, "y"));
+            offset.bound(outline(target, depth + 1)?)
+        }
+        _ => node
+            .children()
+            .filter(Node::is_element)
+            .find_map(|child| outline(child, depth))?,
+    };
+    Some(transform(node).bound(rect))
+}
+
+/// Works out the outline of an element in the coordinates of the whole
+/// SVG, moved by whatever groups it's in.
+fn extent(node: Node) -> Option<Rect> {
+    let placed = node
+        .ancestors()
+        .skip(1)
+        .fold(Transform::IDENTITY, |inner, outer| {
+            transform(outer).compose(&inner)
+        });
+    Some(placed.bound(outline(node, 0)?)).filter(|rect| rect.width > 0.0 && rect.height > 0.0)
+}
+
+/// The size of the SVG's page, and how its coordinates map onto it.
+struct Page {
+    width: f64,
+    height: f64,
+    view: Rect,
+}
+
+impl Page {
+    /// Reads the page from the root `svg` element. There's no page if it
+    /// doesn't say how big it is.
+    fn new(root: Node) -> Option<Self> {
+        let size = |name| root.attribute(name).and_then(length).filter(|n| *n > 0.0);
+        let (width, height) = (size("width"), size("height"));
+        let view = match root.attribute("viewBox").map(numbers).as_deref() {
+            Some(&[x, y, width, height]) => Rect {
+                x,
+                y,
+                width,
+                height,
+            },
+            _ => Rect {
+                x: 0.0,
+                y: 0.0,
+                width: width?,
+                height: height?,
+            },
+        };
+        let page = Self {
+            width: width.unwrap_or(view.width),
+            height: height.unwrap_or(view.height),
+            view,
+        };
+        [page.width, page.height, view.width, view.height]
+            .iter()
+            .all(|n| n.is_finite() && *n > 0.0)
+            .then_some(page)
+    }
+
+    /// Where a rectangle in the SVG's coordinates ends up on the page.
+    fn place(&self, rect: Rect) -> Rect {
+        let sx = self.width / self.view.width;
+        let sy = self.height / self.view.height;
+        Rect {
+            x: (rect.x - self.view.x) * sx,
+            y: (rect.y - self.view.y) * sy,
+            width: rect.width * sx,
+            height: rect.height * sy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A deck with only a few cards in it, drawn in the different ways decks
+    /// can be.
+    const DECK: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd">
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"
+     width="50mm" height='25mm' viewBox="0 0 200 100">
+  <defs>
+    <rect id="card" width="40" height="60"/>
+  </defs>
+  <g transform="translate(10 20)">
+    <g id='club_1'>
+      <path d="M0 0h1"/>
+      <rect x="5" y="0" width="40px" height="60"/>
+    </g>
+  </g>
+  <g id="heart_queen" transform="translate(100 0)">
+    <g>
+      <use xlink:href="#card" x="10" y="5"/>
+    </g>
+  </g>
+  <g id="back">
+    <path d="M0 0h1"/>
+  </g>
+  <rect x="150" y="40" width="40" height="60"/>
+</svg>"##;
+
+    fn outline_of(id: &str) -> Option<Rect> {
+        let svg = Document::parse_with_options(
+            DECK,
+            ParsingOptions {
+                allow_dtd: true,
+                ..ParsingOptions::default()
+            },
+        )
+        .unwrap();
+        extent(element(&svg, id)?)
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn transforms_are_read_in_order() {
+        let tests = vec![
+            ("translate(10 20) scale(2)", (1.0, 1.0), (12.0, 22.0)),
+            ("scale(2) translate(10, 20)", (1.0, 1.0), (22.0, 42.0)),
+            ("matrix(1 0 0 1 5 6)", (1.0, 1.0), (6.0, 7.0)),
+            ("rotate(90 10 10)", (20.0, 10.0), (10.0, 20.0)),
+            ("skewX(30) translate(1,2)", (0.0, 0.0), (1.0, 2.0)),
+            ("", (3.0, 4.0), (3.0, 4.0)),
+        ];
+        for (text, point, expected) in tests {
+            let (x, y) = Transform::parse(text).apply(point);
+            assert!(close(x, expected.0) && close(y, expected.1), "{}", text);
+        }
+    }
+
+    #[test]
+    fn cards_are_outlined_by_their_first_rectangle() {
+        let club = Rect {
+            x: 15.0,
+            y: 20.0,
+            width: 40.0,
+            height: 60.0,
+        };
+        assert_eq!(Some(club), outline_of("club_1"));
+        let queen = Rect {
+            x: 110.0,
+            y: 5.0,
+            width: 40.0,
+            height: 60.0,
+        };
+        assert_eq!(Some(queen), outline_of("heart_queen"));
+        // The rectangle after the back isn't part of it.
+        assert_eq!(None, outline_of("back"));
+        assert_eq!(None, outline_of("spade_king"));
+    }
+
+    #[test]
+    fn pages_are_measured_in_pixels() {
+        let svg = Document::parse_with_options(
+            DECK,
+            ParsingOptions {
+                allow_dtd: true,
+                ..ParsingOptions::default()
+            },
+        )
+        .unwrap();
+        let page = Page::new(svg.root_element()).unwrap();
+        assert!(close(page.width, 50.0 * 96.0 / 25.4));
+        let placed = page.place(Rect {
+            x: 100.0,
+            y: 50.0,
+            width: 100.0,
+            height: 50.0,
+        });
+        assert!(close(placed.x, page.width / 2.0) && close(placed.width, page.width / 2.0));
+        assert!(close(placed.y, page.height / 2.0) && close(placed.height, page.height / 2.0));
+
+        let page = |root: &str| {
+            let svg = Document::parse(root).unwrap();
+            Page::new(svg.root_element()).map(|page| (page.width, page.height))
+        };
+        assert_eq!(
+            Some((200.0, 100.0)),
+            page(r#"<svg viewBox="0 0 200 100"/>"#)
+        );
+        assert_eq!(
+            Some((200.0, 100.0)),
+            page(r#"<svg width="100%" height="100%" viewBox="0 0 200 100"/>"#)
+        );
+        assert_eq!(
+            Some((72.0, 48.0)),
+            page(r#"<svg width="54pt" height="0.5in"/>"#)
+        );
+        assert_eq!(None, page(r#"<svg width="100%" height="100%"/>"#));
+        assert_eq!(None, page(r#"<svg viewBox="0 0 0 100"/>"#));
+    }
+
+    #[test]
+    fn cards_have_aisleriot_ids() {
+        let tests = vec![
+            ("AC", "club_1"),
+            ("TD", "diamond_10"),
+            ("QH", "heart_queen"),
+            ("KS", "spade_king"),
+        ];
+        for (card, id) in tests {
+            assert_eq!(id, card_id(Card::from(card)));
+        }
+    }
+
+    #[test]
+    fn the_built_in_deck_has_every_card() {
+        let layout = DeckLayout::read(include_str!("../data/anglo.svg")).unwrap();
+        assert_eq!(52, layout.cards.len());
+        for rect in layout.cards.values().chain([&layout.back]) {
+            assert!(rect.x >= 0.0 && rect.x + rect.width <= layout.width + 1.0);
+            assert!(rect.y >= 0.0 && rect.y + rect.height <= layout.height + 1.0);
+            assert!(close(rect.height, layout.back.height));
+        }
+    }
+}
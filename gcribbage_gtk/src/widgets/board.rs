@@ -0,0 +1,407 @@
+use gcribbage_lib::player::PlayerPosition;
+use relm4::gtk::{
+    self,
+    cairo::Context,
+    glib::{self, clone, BoxedAnyObject, Properties},
+    prelude::*,
+    subclass::prelude::*,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// How long a peg takes to jump to its new hole, in microseconds.
+const JUMP_TIME: f64 = 600_000.0;
+
+/// The board is laid out in this many streets, going back and forth.
+const STREETS: u8 = 3;
+
+/// The holes are grouped in fives along each street.
+const GROUP: u8 = 5;
+
+/// Peg colours, in seat order.
+const COLOURS: [(f64, f64, f64); 4] = [
+    (0.8, 0.1, 0.1),
+    (0.1, 0.3, 0.8),
+    (0.1, 0.6, 0.2),
+    (0.9, 0.7, 0.1),
+];
+
+/// Where a player's two pegs are. The front peg marks the score, and the
+/// back peg marks the score before the last points were pegged.
+#[derive(Debug, Default, Clone, Copy)]
+struct Pegs {
+    front: u8,
+    back: u8,
+}
+
+/// A peg on its way from one hole to another.
+#[derive(Debug, Clone, Copy)]
+struct Jump {
+    from: u8,
+    to: u8,
+    /// The frame time the jump started at, once there's been a frame.
+    started: Option<i64>,
+    /// How far through the jump the peg is, from 0 to 1.
+    progress: f64,
+}
+
+#[derive(Properties)]
+#[properties(wrapper_type = super::CribbageBoard)]
+pub struct CribbageBoard {
+    /// The score of each player, as a `HashMap<PlayerPosition, u8>`.
+    #[property(get, set)]
+    scores: RefCell<BoxedAnyObject>,
+    #[property(get, set)]
+    target_score: RefCell<u8>,
+    /// Where to mark the skunk line. Zero means not to mark it.
+    #[property(get, set)]
+    skunk_line: RefCell<u8>,
+    /// Where to mark the double skunk line. Zero means not to mark it.
+    #[property(get, set)]
+    double_skunk_line: RefCell<u8>,
+    /// How long the pegs take to move, as a multiple of their usual time.
+    /// Zero means they go straight to their new holes.
+    #[property(get, set)]
+    animation_time: RefCell<f64>,
+    pegs: RefCell<HashMap<PlayerPosition, Pegs>>,
+    jumps: RefCell<HashMap<PlayerPosition, Jump>>,
+}
+
+impl Default for CribbageBoard {
+    fn default() -> Self {
+        Self {
+            scores: RefCell::new(BoxedAnyObject::new::<HashMap<PlayerPosition, u8>>(
+                HashMap::new(),
+            )),
+            target_score: RefCell::new(121),
+            skunk_line: RefCell::new(91),
+            double_skunk_line: RefCell::new(61),
+            animation_time: RefCell::new(1.0),
+            pegs: RefCell::default(),
+            jumps: RefCell::default(),
+        }
+    }
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for CribbageBoard {
+    const NAME: &'static str = "CribbageBoard";
+    type Type = super::CribbageBoard;
+    type ParentType = gtk::DrawingArea;
+}
+
+/// Works out where the holes go on a board of a given size.
+struct Layout {
+    holes_per_street: u8,
+    target: u8,
+    lanes: usize,
+    hole_gap: f64,
+    lane_gap: f64,
+    street_height: f64,
+    left: f64,
+}
+
+impl Layout {
+    fn new(width: f64, height: f64, target: u8, lanes: usize) -> Self {
+        let holes_per_street = (target.saturating_sub(1) / STREETS).max(1);
+        let groups = holes_per_street.div_ceil(GROUP);
+        // Each group of holes gets an extra gap after it, and there's room
+        // left at the ends for the start and game holes.
+        let columns = (holes_per_street + groups + 4) as f64;
+        let hole_gap = width / columns;
+        let street_height = height / STREETS as f64;
+        Self {
+            holes_per_street,
+            target,
+            lanes: lanes.max(1),
+            hole_gap,
+            lane_gap: street_height / (lanes.max(1) + 1) as f64,
+            street_height,
+            left: hole_gap * 2.0,
+        }
+    }
+
+    fn hole_radius(&self) -> f64 {
+        (self.hole_gap.min(self.lane_gap) * 0.25).max(1.0)
+    }
+
+    fn lane_y(&self, street: u8, lane: usize) -> f64 {
+        street as f64 * self.street_height + (lane + 1) as f64 * self.lane_gap
+    }
+
+    /// How far along a street a hole is, allowing for the gaps between
+    /// groups.
+    fn column_x(&self, column: u8) -> f64 {
+        let gaps = column / GROUP;
+        self.left + (column + gaps) as f64 * self.hole_gap
+    }
+
+    /// Where the given hole is for the given lane. Hole zero is the start,
+    /// off the beginning of the first street, and the target is the game
+    /// hole off the end of the last one.
+    fn hole(&self, lane: usize, hole: u8) -> (f64, f64) {
+        if hole == 0 {
+            return (self.hole_gap, self.lane_y(0, lane));
+        }
+        if hole >= self.target {
+            let last = self.column_x(self.holes_per_street - 1);
+            return (
+                last + self.hole_gap * 2.0,
+                (STREETS as f64 - 0.5) * self.street_height,
+            );
+        }
+        let street = ((hole - 1) / self.holes_per_street).min(STREETS - 1);
+        let column = (hole - 1) - street * self.holes_per_street;
+        let column = column.min(self.holes_per_street - 1);
+        // Every other street runs back the other way.
+        let column = match street % 2 {
+            0 => column,
+            _ => self.holes_per_street - 1 - column,
+        };
+        (self.column_x(column), self.lane_y(street, lane))
+    }
+
+    /// Where a peg a fraction of the way along the track between two
+    /// holes is, following the track around corners.
+    fn between(&self, lane: usize, from: u8, to: u8, progress: f64) -> (f64, f64) {
+        let along = from as f64 + (to as f64 - from as f64) * progress;
+        let before = along.floor() as u8;
+        let after = along.ceil() as u8;
+        let (x0, y0) = self.hole(lane, before);
+        let (x1, y1) = self.hole(lane, after);
+        let t = along - along.floor();
+        (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t)
+    }
+}
+
+impl CribbageBoard {
+    /// The players with pegs on the board, in seat order.
+    fn players(&self) -> Vec<PlayerPosition> {
+        let scores = self.scores.borrow();
+        let scores = scores.borrow::<HashMap<PlayerPosition, u8>>();
+        PlayerPosition::iter(COLOURS.len())
+            .filter(|player| scores.contains_key(player))
+            .collect()
+    }
+
+    fn draw(&self, cr: &Context, width: i32, height: i32) {
+        let players = self.players();
+        let layout = Layout::new(
+            width as f64,
+            height as f64,
+            *self.target_score.borrow(),
+            players.len(),
+        );
+        let radius = layout.hole_radius();
+
+        // The board itself.
+        cr.set_source_rgb(0.72, 0.55, 0.33);
+        cr.rectangle(0.0, 0.0, width as f64, height as f64);
+        cr.fill().expect("Could not draw board");
+
+        // The holes.
+        cr.set_source_rgb(0.25, 0.15, 0.05);
+        for lane in 0..layout.lanes {
+            for hole in 0..=layout.target {
+                let (x, y) = layout.hole(lane, hole);
+                cr.arc(x, y, radius, 0.0, 2.0 * PI);
+                cr.fill().expect("Could not draw hole");
+            }
+        }
+
+        // The skunk lines go between the last hole that is skunked and the
+        // first one that isn't.
+        cr.set_line_width(radius / 2.0);
+        for (line, dashes) in [
+            (*self.skunk_line.borrow(), vec![]),
+            (*self.double_skunk_line.borrow(), vec![radius, radius]),
+        ] {
+            if line <= 1 || line >= layout.target {
+                continue;
+            }
+            let (x0, y) = layout.hole(0, line - 1);
+            let (x1, _) = layout.hole(0, line);
+            let x = (x0 + x1) / 2.0;
+            let top = y - layout.lane_gap / 2.0;
+            let bottom = top + layout.lane_gap * layout.lanes as f64;
+            cr.set_dash(&dashes, 0.0);
+            cr.move_to(x, top);
+            cr.line_to(x, bottom);
+            cr.stroke().expect("Could not draw skunk line");
+        }
+        cr.set_dash(&[], 0.0);
+
+        // The pegs.
+        let pegs = self.pegs.borrow();
+        let jumps = self.jumps.borrow();
+        for (lane, player) in players.iter().enumerate() {
+            let (r, g, b) = COLOURS[lane % COLOURS.len()];
+            cr.set_source_rgb(r, g, b);
+            let pegs = pegs.get(player).copied().unwrap_or_default();
+            let front = match jumps.get(player) {
+                Some(jump) => layout.between(lane, jump.from, jump.to, jump.progress),
+                None => layout.hole(lane, pegs.front),
+            };
+            for (x, y) in [layout.hole(lane, pegs.back), front] {
+                cr.arc(x, y, radius * 1.6, 0.0, 2.0 * PI);
+                cr.fill().expect("Could not draw peg");
+            }
+        }
+    }
+
+    /// Moves the pegs to match the scores. A player whose score went up
+    /// leapfrogs their back peg past the front one, and it jumps along the
+    /// board to its new hole. A score going down means a new game, so the
+    /// pegs go straight back.
+    fn update_pegs(&self) {
+        let scores = self
+            .scores
+            .borrow()
+            .borrow::<HashMap<PlayerPosition, u8>>()
+            .clone();
+        let mut pegs = self.pegs.borrow_mut();
+        let mut jumps = self.jumps.borrow_mut();
+        let animated = *self.animation_time.borrow() > 0.0;
+        pegs.retain(|player, _| scores.contains_key(player));
+        for (player, score) in scores {
+            let current = pegs.entry(player).or_default();
+            if score == current.front {
+                continue;
+            }
+            if score < current.front {
+                *current = Pegs {
+                    front: score,
+                    back: 0,
+                };
+                jumps.remove(&player);
+                continue;
+            }
+            if !animated {
+                jumps.remove(&player);
+                *current = Pegs {
+                    front: score,
+                    back: current.front,
+                };
+                continue;
+            }
+            jumps.insert(
+                player,
+                Jump {
+                    from: current.back,
+                    to: score,
+                    started: None,
+                    progress: 0.0,
+                },
+            );
+            *current = Pegs {
+                front: score,
+                back: current.front,
+            };
+        }
+        drop(pegs);
+        if !jumps.is_empty() {
+            drop(jumps);
+            self.start_jumping();
+        }
+    }
+
+    fn start_jumping(&self) {
+        let jump_time = JUMP_TIME * *self.animation_time.borrow();
+        self.obj().add_tick_callback(move |board, clock| {
+            let now = clock.frame_time();
+            let mut jumps = board.imp().jumps.borrow_mut();
+            for jump in jumps.values_mut() {
+                let started = *jump.started.get_or_insert(now);
+                jump.progress = ((now - started) as f64 / jump_time).min(1.0);
+            }
+            jumps.retain(|_, jump| jump.progress < 1.0);
+            let done = jumps.is_empty();
+            drop(jumps);
+            board.queue_draw();
+            glib::Continue(!done)
+        });
+    }
+}
+
+impl ObjectImpl for CribbageBoard {
+    fn constructed(&self) {
+        self.parent_constructed();
+        let obj = self.obj();
+        DrawingAreaExtManual::set_draw_func(
+            obj.as_ref(),
+            clone!(@weak self as widget => move |_, cr, w, h| widget.draw(cr, w, h)),
+        );
+    }
+
+    fn properties() -> &'static [glib::ParamSpec] {
+        Self::derived_properties()
+    }
+
+    fn set_property(&self, id: usize, value: &glib::Value, pspec: &glib::ParamSpec) {
+        self.derived_set_property(id, value, pspec);
+        if pspec.name() == "scores" {
+            self.update_pegs();
+        }
+        self.obj().queue_draw();
+    }
+
+    fn property(&self, id: usize, pspec: &glib::ParamSpec) -> glib::Value {
+        self.derived_property(id, pspec)
+    }
+}
+
+impl WidgetImpl for CribbageBoard {}
+impl DrawingAreaImpl for CribbageBoard {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_hole_has_a_place_of_its_own() {
+        for target in [61, 121] {
+            let layout = Layout::new(600.0, 150.0, target, 2);
+            for lane in 0..2 {
+                let holes = (0..=target)
+                    .map(|hole| layout.hole(lane, hole))
+                    .collect::<Vec<(f64, f64)>>();
+                for (i, (x, y)) in holes.iter().enumerate() {
+                    assert!(*x > 0.0 && *x < 600.0 && *y > 0.0 && *y < 150.0);
+                    assert!(holes[..i].iter().all(|other| other != &(*x, *y)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn streets_turn_back_at_their_ends() {
+        for (target, street) in [(61, 20), (121, 40)] {
+            let layout = Layout::new(600.0, 150.0, target, 2);
+            assert_eq!(street, layout.holes_per_street);
+            let hole = |hole| layout.hole(0, hole);
+            // The first street runs left to right, and the next comes back
+            // underneath it.
+            assert!(hole(1).0 < hole(street).0);
+            assert_eq!(hole(1).1, hole(street).1);
+            assert_eq!(hole(street).0, hole(street + 1).0);
+            assert!(hole(street).1 < hole(street + 1).1);
+            assert_eq!(hole(1).0, hole(street * 2).0);
+            // The start is before the first hole and the game hole after
+            // the last.
+            assert!(hole(0).0 < hole(1).0);
+            assert!(hole(target).0 > hole(target - 1).0);
+        }
+    }
+
+    #[test]
+    fn holes_come_in_fives() {
+        let layout = Layout::new(600.0, 150.0, 121, 2);
+        let x = |hole| layout.hole(0, hole).0;
+        let gap = x(2) - x(1);
+        assert!((x(5) - x(4) - gap).abs() < 1e-9);
+        assert!((x(6) - x(5) - gap * 2.0).abs() < 1e-9);
+        assert!(layout.hole(0, 1).1 < layout.hole(1, 1).1);
+        assert_eq!((x(1) + x(2)) / 2.0, layout.between(0, 1, 2, 0.5).0);
+    }
+}
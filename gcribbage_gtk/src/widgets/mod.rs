@@ -1,10 +1,40 @@
+mod board;
+#[allow(clippy::module_inception)]
 mod widgets;
 
+use gcribbage_lib::deck::Card;
 use relm4::gtk::{
     self,
-    glib::{self, Object},
+    glib::{self, BoxedAnyObject, Object},
+    prelude::*,
 };
 
+/// A card as it lies on the table. Nobody can tell what a face down card
+/// is, so that's all there is to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableCard {
+    FaceUp(Card),
+    FaceDown,
+}
+
+impl TableCard {
+    /// The card, if it can be seen.
+    pub fn card(&self) -> Option<Card> {
+        match self {
+            TableCard::FaceUp(card) => Some(*card),
+            TableCard::FaceDown => None,
+        }
+    }
+
+    pub fn face_up(cards: &[Card]) -> Vec<TableCard> {
+        cards.iter().map(|card| TableCard::FaceUp(*card)).collect()
+    }
+
+    pub fn face_down(cards: &[Card]) -> Vec<TableCard> {
+        vec![TableCard::FaceDown; cards.len()]
+    }
+}
+
 glib::wrapper! {
     pub struct CardBox(ObjectSubclass<widgets::CardBox>)
         @extends gtk::DrawingArea, gtk::Widget,
@@ -15,6 +45,24 @@ impl CardBox {
     pub fn new() -> Self {
         Object::builder().build()
     }
+
+    /// Calls the given function with the cards each time a full choice of
+    /// them has been clicked on.
+    pub fn connect_cards_chosen<F: Fn(&Self, Vec<Card>) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_local("cards-chosen", false, move |values| {
+            let cards_box = values[0]
+                .get::<Self>()
+                .expect("Signal sent by something else");
+            let cards = values[1]
+                .get::<BoxedAnyObject>()
+                .expect("Signal sent without cards");
+            f(&cards_box, cards.borrow::<Vec<Card>>().clone());
+            None
+        })
+    }
 }
 
 impl Default for CardBox {
@@ -22,3 +70,21 @@ impl Default for CardBox {
         Self::new()
     }
 }
+
+glib::wrapper! {
+    pub struct CribbageBoard(ObjectSubclass<board::CribbageBoard>)
+        @extends gtk::DrawingArea, gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl CribbageBoard {
+    pub fn new() -> Self {
+        Object::builder().build()
+    }
+}
+
+impl Default for CribbageBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
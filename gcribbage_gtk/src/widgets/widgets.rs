@@ -1,112 +1,263 @@
-use gcribbage_lib::deck::{Card, Rank, Suit};
+use super::TableCard;
+use crate::theme::{self, DeckImages};
+use gcribbage_lib::deck::Card;
 use relm4::gtk::{
     self,
     cairo::Context,
-    gdk::prelude::GdkCairoContextExt,
+    gdk::{self, prelude::GdkCairoContextExt},
     gdk_pixbuf::Pixbuf,
-    glib::{self, clone, Properties},
+    glib::BoxedAnyObject,
+    glib::{self, clone, subclass::Signal, Properties},
     prelude::*,
     subclass::prelude::*,
-    glib::BoxedAnyObject,
 };
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+/// How much of a selectable box's height is kept free for raising the
+/// selected cards into.
+const RAISE: f64 = 0.1;
+
+/// How long a card takes to slide into place, in microseconds.
+const SLIDE_TIME: f64 = 350_000.0;
+
+/// How long to wait before sliding in each card after the first, when
+/// several arrive at once.
+const SLIDE_STAGGER: f64 = 70_000.0;
+
+/// How long a card takes to turn over, in microseconds.
+const FLIP_TIME: f64 = 300_000.0;
+
+/// How a card is getting to its place in the box.
+#[derive(Debug, Clone, Copy)]
+enum Movement {
+    /// Sliding in from this point.
+    Slide(f64, f64),
+    /// Turning over from face down.
+    Flip,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Motion {
+    movement: Movement,
+    /// How long to wait before starting, in microseconds.
+    delay: f64,
+    /// How long the motion takes, as a multiple of its usual time.
+    time: f64,
+    /// The frame time the motion was started at, once there's been a
+    /// frame.
+    started: Option<i64>,
+    /// How far through the motion the card is, from 0 to 1.
+    progress: f64,
+}
+
+impl Motion {
+    fn new(movement: Movement, delay: f64, time: f64) -> Self {
+        Self {
+            movement,
+            delay: delay * time,
+            time,
+            started: None,
+            progress: 0.0,
+        }
+    }
+
+    fn duration(&self) -> f64 {
+        let duration = match self.movement {
+            Movement::Slide(..) => SLIDE_TIME,
+            Movement::Flip => FLIP_TIME,
+        };
+        duration * self.time
+    }
+
+    /// Moves the motion along to the given frame time.
+    fn tick(&mut self, now: i64) {
+        let started = *self.started.get_or_insert(now);
+        let elapsed = (now - started) as f64 - self.delay;
+        self.progress = (elapsed / self.duration()).clamp(0.0, 1.0);
+    }
 
-/// A structure for holding a Pixbuf that represents a deck of cards.
+    fn waiting(&self) -> bool {
+        self.started.is_none() || self.progress <= 0.0
+    }
+
+    /// How far through the motion the card looks to be. Cards slow down as
+    /// they come to a stop.
+    fn eased(&self) -> f64 {
+        1.0 - (1.0 - self.progress).powi(3)
+    }
+}
+
+/// A structure for holding the card images from a deck, drawn at a
+/// particular height. The images themselves are shared with every other
+/// widget drawing the same cards at the same size.
+#[derive(Default)]
 struct CardBuffer {
-    buffer: Option<Pixbuf>,
-    sub_pixbufs: HashMap<Card, Pixbuf>,
+    /// Which deck the cards were drawn from, how many pixels tall, and
+    /// whether in four colours.
+    drawn: Option<(String, i32, bool)>,
+    images: Option<Rc<DeckImages>>,
 }
 
 impl CardBuffer {
-    pub fn has_buffer(&self) -> bool {
-        self.buffer.is_some()
-    }
-
-    /// Create a Pixbuf of the given height that has the card images as
-    /// loaded from an SVG.
-    ///
-    /// The width will match the aspect ration with respect to the height.
-    pub fn create_buffer(&mut self, height: i32) {
-        // We're going to rebuild the card images, so clear
-        // what we already have.
-        self.sub_pixbufs.clear();
-        self.buffer = Some(
-            Pixbuf::from_resource_at_scale(
-                "/com/ronsbrain/gcribbage_gtk/anglo.svg",
-                -1, // Make the width match the origin aspect ratio wrt the height
-                height * 5,
-                true,
-            )
-            .expect("Could not create card buffer"),
-        );
+    pub fn is_drawn(&self, theme: &str, height: i32, four_colour: bool) -> bool {
+        self.drawn
+            .as_ref()
+            .is_some_and(|(t, h, f)| t == theme && *h == height && *f == four_colour)
+    }
 
-        // Build out the HashMap of Card to sub pixbufs
-        // that represent the card images.
-        let buffer = self.buffer.as_ref().unwrap();
-        let width = buffer.width() / 13;
-        let height = buffer.height() / 5;
-        for suit in Suit::iter() {
-            // The iterator for Suit doesn't go in the order
-            // that we expect for the SVG, so I guess we just
-            // remap it for now.
-            //
-            // TODO: Eliminate this logic and either make the
-            // iterator match the suit sequence in the SVG or
-            // figure out if there's a way to use the SVG id's
-            // to pick out individual cards.
-            let row = match suit {
-                Suit::Clubs => 0,
-                Suit::Diamonds => 1,
-                Suit::Hearts => 2,
-                Suit::Spades => 3,
-            };
-            for rank in Rank::iter() {
-                let col = rank.ordinal() - 1;
-                let sub_pixbuf =
-                    buffer.new_subpixbuf(width * col as i32, height * row, width, height);
-                self.sub_pixbufs.insert(Card::new(suit, rank), sub_pixbuf);
-            }
-        }
+    pub fn create_buffer(&mut self, theme: &str, height: i32, four_colour: bool) {
+        self.images = Some(theme::card_images(theme, height, four_colour));
+        self.drawn = Some((String::from(theme), height, four_colour));
+    }
+
+    fn images(&self) -> &DeckImages {
+        self.images
+            .as_ref()
+            .expect("Requested pixbuf before loading")
     }
 
     pub fn get_pixbuf_for(&self, card: Card) -> &Pixbuf {
-        self.sub_pixbufs
+        self.images()
+            .cards
             .get(&card)
             .expect("Requested pixbuf for imaginary card")
     }
+
+    pub fn get_back_pixbuf(&self) -> &Pixbuf {
+        &self.images().back
+    }
+
+    /// The width of a single card in pixels, if the cards have been loaded.
+    pub fn card_width(&self) -> Option<f64> {
+        self.images
+            .as_ref()
+            .map(|images| images.back.width() as f64)
+    }
 }
 
-impl Default for CardBuffer {
-    fn default() -> Self {
-        Self {
-            buffer: None,
-            sub_pixbufs: HashMap::new(),
-        }
+/// Where the cards in a box are drawn. Each is `offset` along from the
+/// last, and the ones that aren't selected are `raise` further down.
+struct Spread {
+    offset: f64,
+    card_width: f64,
+    card_height: f64,
+    raise: f64,
+}
+
+impl Spread {
+    /// Where the top left corner of the card at the given place in the hand
+    /// is drawn.
+    fn corner(&self, i: usize, raised: bool) -> (f64, f64) {
+        let top = if raised { 0.0 } else { self.raise };
+        (i as f64 * self.offset, top)
     }
+
+    /// Finds where in the hand the card drawn at the given point is. Cards
+    /// are drawn left to right, each on top of the last, so the rightmost
+    /// card under the point is the one that can be seen.
+    fn card_at(&self, cards: &[TableCard], selected: &[Card], x: f64, y: f64) -> Option<usize> {
+        cards.iter().enumerate().rposition(|(i, card)| {
+            let raised = card.card().is_some_and(|c| selected.contains(&c));
+            let (left, top) = self.corner(i, raised);
+            x >= left && x < left + self.card_width && y >= top && y < top + self.card_height
+        })
+    }
+}
+
+/// Where the focus goes from the card it's on, by where it is in the hand,
+/// moving to the next face up card along or the one before. If nothing has
+/// the focus yet, it goes to the card at that end.
+fn next_focus(cards: &[TableCard], focused: Option<usize>, forward: bool) -> Option<usize> {
+    let faces = cards
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| card.card().is_some())
+        .map(|(i, _)| i)
+        .collect::<Vec<usize>>();
+    let at = focused.and_then(|f| faces.iter().position(|i| *i == f));
+    let next = match (at, forward) {
+        (Some(at), true) => at + 1,
+        (Some(at), false) => at.saturating_sub(1),
+        (None, true) => 0,
+        (None, false) => faces.len().saturating_sub(1),
+    };
+    faces.get(next.min(faces.len().saturating_sub(1))).copied()
 }
 
 #[derive(Properties)]
 #[properties(wrapper_type = super::CardBox)]
 pub struct CardBox {
-    // Each CardBox will have its own CardBuffer. This can cause
-    // some duplication but
-    //  - It's better than having a static reference and unsafe calls
-    //  - It's possible that there are use cases that want to have
-    //    differently sized cards
+    // Each CardBox keeps hold of the card images for the size it draws
+    // them at. The images come from a cache, so boxes that draw cards the
+    // same size share them.
     card_buffer: RefCell<CardBuffer>,
+    /// The cards to draw, as a `Vec<TableCard>`.
     #[property(get, set)]
     hand: RefCell<BoxedAnyObject>,
     #[property(get, set)]
     offset: RefCell<f64>,
+    /// The name of the deck to draw the cards from.
+    #[property(get, set)]
+    theme: RefCell<String>,
+    /// Whether to draw the cards in high contrast, with each suit in its
+    /// own colour.
+    #[property(get, set)]
+    four_colour: RefCell<bool>,
+    /// What the cards are, such as "Your hand", for screen readers.
+    #[property(get, set)]
+    label: RefCell<String>,
+    /// Whether the cards can be clicked on. Selected cards are raised, so
+    /// the cards in a selectable box are drawn a little shorter to leave
+    /// them room.
+    #[property(get, set)]
+    selectable: RefCell<bool>,
+    /// How many cards make a choice. Once that many are selected, they are
+    /// sent out with the `cards-chosen` signal. Nothing can be selected
+    /// while this is zero.
+    #[property(get, set)]
+    choose_count: RefCell<u32>,
+    selected: RefCell<Vec<Card>>,
+    /// Where the keyboard focus is, by where the card is in the hand.
+    focused: RefCell<Option<usize>>,
+    /// Cards to pick out from the rest, as a `Vec<Card>`, such as the ones
+    /// making up a score as it's counted.
+    #[property(get, set)]
+    highlighted: RefCell<BoxedAnyObject>,
+    /// Where new cards slide in from. Without one, they just appear.
+    #[property(get, set, nullable)]
+    origin: RefCell<Option<gtk::Widget>>,
+    /// How long the cards take to move, as a multiple of their usual
+    /// time. Zero means they go straight to their places.
+    #[property(get, set)]
+    animation_time: RefCell<f64>,
+    /// How each card is getting into its place, by where it is in the hand.
+    motions: RefCell<Vec<Option<Motion>>>,
+    /// A child for each card, by where it is in the hand, for screen
+    /// readers to go through the cards one at a time. They're empty, and
+    /// just placed where their cards are drawn.
+    items: RefCell<Vec<gtk::Widget>>,
 }
 
 impl Default for CardBox {
     fn default() -> Self {
         Self {
             card_buffer: RefCell::default(),
-            hand: RefCell::new(BoxedAnyObject::new::<Vec<Card>>(vec![])),
-            offset: RefCell::new(20.0), 
+            hand: RefCell::new(BoxedAnyObject::new::<Vec<TableCard>>(vec![])),
+            offset: RefCell::new(20.0),
+            theme: RefCell::new(String::from(theme::BUILTIN)),
+            four_colour: RefCell::new(false),
+            label: RefCell::default(),
+            selectable: RefCell::new(false),
+            choose_count: RefCell::new(0),
+            selected: RefCell::default(),
+            focused: RefCell::default(),
+            highlighted: RefCell::new(BoxedAnyObject::new::<Vec<Card>>(vec![])),
+            origin: RefCell::default(),
+            animation_time: RefCell::new(1.0),
+            motions: RefCell::default(),
+            items: RefCell::default(),
         }
     }
 }
@@ -116,20 +267,403 @@ impl ObjectSubclass for CardBox {
     const NAME: &'static str = "CardBox";
     type Type = super::CardBox;
     type ParentType = gtk::DrawingArea;
+
+    fn class_init(klass: &mut Self::Class) {
+        klass.set_accessible_role(gtk::AccessibleRole::List);
+    }
 }
 
 impl CardBox {
+    /// How tall the cards are drawn in a box of the given height, and how
+    /// far the cards that aren't selected are drawn below the ones that are.
+    fn card_height(&self, height: i32) -> (i32, f64) {
+        match *self.selectable.borrow() {
+            true => {
+                let raise = (height as f64 * RAISE).round();
+                (height - raise as i32, raise)
+            }
+            false => (height, 0.0),
+        }
+    }
+
     fn draw(&self, cr: &Context, _width: i32, height: i32) {
+        let (card_height, raise) = self.card_height(height);
+        // On a HiDPI screen the cards are drawn with more pixels than the
+        // widget's size says, so they stay sharp.
+        let scale = self.obj().scale_factor();
+        let theme = self.theme.borrow();
+        let four_colour = *self.four_colour.borrow();
         let mut buffer = self.card_buffer.borrow_mut();
-        if !buffer.has_buffer() {
-            buffer.create_buffer(height);
+        if !buffer.is_drawn(&theme, card_height * scale, four_colour) {
+            buffer.create_buffer(&theme, card_height * scale, four_colour);
+            // The cards' children can't be placed until it's known how wide
+            // the cards are, and the box can't be laid out while it's
+            // being drawn.
+            let obj = self.obj();
+            glib::idle_add_local_once(clone!(@weak obj => move || obj.queue_allocate()));
         }
+        cr.scale(1.0 / scale as f64, 1.0 / scale as f64);
+        let scale = scale as f64;
         let offset = *self.offset.borrow();
-        let cards = self.hand.borrow().borrow::<Vec<Card>>().clone();
+        let selected = self.selected.borrow();
+        // The focus ring is only drawn while the keyboard is in this box.
+        let focused = match self.obj().has_focus() {
+            true => *self.focused.borrow(),
+            false => None,
+        };
+        let highlighted = self.highlighted.borrow();
+        let highlighted = highlighted.borrow::<Vec<Card>>();
+        let motions = self.motions.borrow();
+        let cards = self.cards();
         for (i, card) in cards.iter().enumerate() {
-            let pixbuf = buffer.get_pixbuf_for(*card);
-            GdkCairoContextExt::set_source_pixbuf(cr, pixbuf, i as f64 * offset, 0.0);
+            let motion = motions.get(i).copied().flatten();
+            let mut x = i as f64 * offset * scale;
+            let is_selected = card.card().is_some_and(|c| selected.contains(&c));
+            let mut y = if is_selected { 0.0 } else { raise * scale };
+            let mut face_up = card.card().is_some();
+            let mut squash = 1.0;
+            if let Some(motion) = motion {
+                match motion.movement {
+                    // A card that hasn't set off yet isn't here yet.
+                    Movement::Slide(..) if motion.waiting() => continue,
+                    Movement::Slide(from_x, from_y) => {
+                        let t = motion.eased();
+                        x = from_x * scale + (x - from_x * scale) * t;
+                        y = from_y * scale + (y - from_y * scale) * t;
+                    }
+                    // A card turning over shows its back until it's on its
+                    // edge, then its face.
+                    Movement::Flip => {
+                        face_up = face_up && motion.progress >= 0.5;
+                        squash = (motion.progress * std::f64::consts::PI).cos().abs();
+                    }
+                }
+            }
+            let pixbuf = match (card, face_up) {
+                (TableCard::FaceUp(card), true) => buffer.get_pixbuf_for(*card),
+                _ => buffer.get_back_pixbuf(),
+            };
+            let width = pixbuf.width() as f64;
+            cr.save().expect("Could not save");
+            cr.translate(x + width * (1.0 - squash) / 2.0, y);
+            cr.scale(squash.max(0.01), 1.0);
+            GdkCairoContextExt::set_source_pixbuf(cr, pixbuf, 0.0, 0.0);
             cr.paint().expect("Could not paint");
+            cr.restore().expect("Could not restore");
+            if is_selected {
+                // Tint the selected cards as well as raising them, so they
+                // still stand out when the box is too short to show much
+                // of a raise.
+                cr.rectangle(x, y, pixbuf.width() as f64, pixbuf.height() as f64);
+                cr.set_source_rgba(0.2, 0.4, 0.9, 0.25);
+                cr.fill().expect("Could not highlight");
+            } else if card.card().is_some_and(|c| highlighted.contains(&c)) {
+                cr.rectangle(x, y, pixbuf.width() as f64, pixbuf.height() as f64);
+                cr.set_source_rgba(0.95, 0.75, 0.1, 0.35);
+                cr.fill().expect("Could not highlight");
+            }
+            if focused == Some(i) {
+                let inset = 1.5 * scale;
+                cr.rectangle(
+                    x + inset,
+                    y + inset,
+                    pixbuf.width() as f64 - inset * 2.0,
+                    pixbuf.height() as f64 - inset * 2.0,
+                );
+                cr.set_source_rgb(0.2, 0.4, 0.9);
+                cr.set_line_width(inset * 2.0);
+                cr.stroke().expect("Could not draw focus ring");
+            }
+        }
+    }
+
+    /// Works out how the cards in a new hand get to where they are. Cards
+    /// that were already there stay put, and cards that were face down in
+    /// the same place turn over. Anything else slides in from the origin,
+    /// one after another.
+    fn start_motions(&self, old: &[TableCard]) {
+        let new = self.cards();
+        let origin = self.origin_point();
+        let time = *self.animation_time.borrow();
+        let mut motions = self.motions.borrow_mut();
+        if time <= 0.0 {
+            *motions = vec![None; new.len()];
+            return;
+        }
+        motions.resize(new.len(), None);
+        let mut arrivals = 0;
+        for (i, card) in new.iter().enumerate() {
+            motions[i] = match (old.get(i), card) {
+                (Some(was), _) if was == card => motions[i],
+                (Some(TableCard::FaceDown), TableCard::FaceUp(_)) => {
+                    Some(Motion::new(Movement::Flip, 0.0, time))
+                }
+                (_, TableCard::FaceUp(_)) if old.contains(card) => None,
+                _ => origin.map(|(x, y)| {
+                    arrivals += 1;
+                    let delay = (arrivals - 1) as f64 * SLIDE_STAGGER;
+                    Motion::new(Movement::Slide(x, y), delay, time)
+                }),
+            };
+        }
+        let moving = motions.iter().any(Option::is_some);
+        drop(motions);
+        if moving {
+            self.animate();
+        }
+    }
+
+    /// Where a card has to start from, in this box, to slide out from the
+    /// middle of the origin.
+    fn origin_point(&self) -> Option<(f64, f64)> {
+        let origin = self.origin.borrow().clone()?;
+        let obj = self.obj();
+        let (x, y) = origin.translate_coordinates(
+            &*obj,
+            origin.width() as f64 / 2.0,
+            origin.height() as f64 / 2.0,
+        )?;
+        let scale = obj.scale_factor() as f64;
+        let (card_height, _) = self.card_height(obj.height());
+        let card_width = self
+            .card_buffer
+            .borrow()
+            .card_width()
+            .map_or(card_height as f64 * 0.65, |width| width / scale);
+        Some((x - card_width / 2.0, y - card_height as f64 / 2.0))
+    }
+
+    fn animate(&self) {
+        self.obj().add_tick_callback(|cards_box, clock| {
+            let now = clock.frame_time();
+            let mut motions = cards_box.imp().motions.borrow_mut();
+            for slot in motions.iter_mut() {
+                if let Some(motion) = slot {
+                    motion.tick(now);
+                    if motion.progress >= 1.0 {
+                        *slot = None;
+                    }
+                }
+            }
+            let done = motions.iter().all(Option::is_none);
+            drop(motions);
+            cards_box.queue_draw();
+            glib::Continue(!done)
+        });
+    }
+
+    fn cards(&self) -> Vec<TableCard> {
+        self.hand.borrow().borrow::<Vec<TableCard>>().clone()
+    }
+
+    /// Where the cards are drawn in a box of the given height, once there
+    /// are card images to tell how wide they are.
+    fn spread(&self, height: i32) -> Option<Spread> {
+        let scale = self.obj().scale_factor() as f64;
+        let (card_height, raise) = self.card_height(height);
+        Some(Spread {
+            offset: *self.offset.borrow(),
+            card_width: self.card_buffer.borrow().card_width()? / scale,
+            card_height: card_height as f64,
+            raise,
+        })
+    }
+
+    /// Finds the face up card drawn at the given point. If the card there
+    /// is face down, there's no telling what it is.
+    fn card_at(&self, x: f64, y: f64) -> Option<Card> {
+        let spread = self.spread(self.obj().height())?;
+        let cards = self.cards();
+        let at = spread.card_at(&cards, &self.selected.borrow(), x, y)?;
+        cards[at].card()
+    }
+
+    /// Selects or unselects the card at the given point. If that makes a
+    /// full choice, the chosen cards are sent out and the selection starts
+    /// again.
+    fn click(&self, x: f64, y: f64) {
+        let Some(card) = self.card_at(x, y) else {
+            return;
+        };
+        *self.focused.borrow_mut() = self.cards().iter().position(|c| c.card() == Some(card));
+        self.toggle(card);
+        self.confirm();
+    }
+
+    /// Selects the card if it isn't selected, or unselects it if it is.
+    /// Nothing more can be selected once there's a full choice.
+    fn toggle(&self, card: Card) {
+        let choose_count = *self.choose_count.borrow() as usize;
+        let mut selected = self.selected.borrow_mut();
+        match selected.iter().position(|c| *c == card) {
+            Some(i) => {
+                selected.remove(i);
+            }
+            None if selected.len() < choose_count => selected.push(card),
+            None => (),
+        }
+        self.refresh();
+    }
+
+    /// Sends out the selected cards if they make a full choice, and starts
+    /// the selection again.
+    fn confirm(&self) {
+        let choose_count = *self.choose_count.borrow() as usize;
+        let chosen = {
+            let mut selected = self.selected.borrow_mut();
+            if choose_count == 0 || selected.len() < choose_count {
+                return;
+            }
+            selected.drain(..).collect::<Vec<Card>>()
+        };
+        self.refresh();
+        self.obj()
+            .emit_by_name::<()>("cards-chosen", &[&BoxedAnyObject::new(chosen)]);
+    }
+
+    fn focused_card(&self) -> Option<Card> {
+        let focused = (*self.focused.borrow())?;
+        self.cards().get(focused)?.card()
+    }
+
+    /// Moves the focus to the next face up card along, or the one before.
+    /// If nothing has the focus yet, it goes to the card at that end.
+    fn move_focus(&self, forward: bool) {
+        let next = next_focus(&self.cards(), *self.focused.borrow(), forward);
+        *self.focused.borrow_mut() = next;
+        self.refresh();
+    }
+
+    /// Handles a key press while the box has the focus, returning whether
+    /// it meant anything. The arrow keys move along the cards, space
+    /// selects one, and enter sends out the choice. If the focused card is
+    /// all that's missing from a full choice, enter takes that too.
+    fn key_pressed(&self, key: gdk::Key) -> bool {
+        match key {
+            gdk::Key::Left | gdk::Key::KP_Left => self.move_focus(false),
+            gdk::Key::Right | gdk::Key::KP_Right => self.move_focus(true),
+            gdk::Key::space | gdk::Key::KP_Space => {
+                if let Some(card) = self.focused_card() {
+                    self.toggle(card);
+                }
+            }
+            gdk::Key::Return | gdk::Key::KP_Enter | gdk::Key::ISO_Enter => {
+                let choose_count = *self.choose_count.borrow() as usize;
+                let missing = choose_count.saturating_sub(self.selected.borrow().len());
+                match self.focused_card() {
+                    Some(card) if missing == 1 && !self.selected.borrow().contains(&card) => {
+                        self.toggle(card);
+                    }
+                    _ => (),
+                }
+                self.confirm();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Redraws the box, and tells screen readers what's in it now. Each
+    /// card has a child of its own, named after the card and marked if
+    /// it's selected, and the box points at the one with the focus.
+    fn refresh(&self) {
+        let obj = self.obj();
+        obj.queue_draw();
+        obj.queue_allocate();
+        let cards = self.cards();
+        let mut items = self.items.borrow_mut();
+        while items.len() > cards.len() {
+            if let Some(item) = items.pop() {
+                item.unparent();
+            }
+        }
+        while items.len() < cards.len() {
+            let item = gtk::Box::builder()
+                .accessible_role(gtk::AccessibleRole::ListItem)
+                .can_target(false)
+                .focusable(false)
+                .build()
+                .upcast::<gtk::Widget>();
+            item.set_parent(&*obj);
+            items.push(item);
+        }
+        let selected = self.selected.borrow();
+        let selectable = *self.selectable.borrow();
+        for (item, card) in items.iter().zip(&cards) {
+            match card.card() {
+                Some(card) => {
+                    item.update_property(&[gtk::accessible::Property::Label(&card.name())]);
+                    item.update_state(&[gtk::accessible::State::Selected(
+                        selectable.then(|| selected.contains(&card)),
+                    )]);
+                }
+                None => {
+                    item.update_property(&[gtk::accessible::Property::Label("face down")]);
+                    item.reset_state(gtk::AccessibleState::Selected);
+                }
+            }
+        }
+
+        let label = self.label.borrow();
+        let label = match (label.is_empty(), cards.is_empty()) {
+            (true, true) => String::from("no cards"),
+            (false, true) => format!("{}: no cards", label),
+            (_, false) => label.clone(),
+        };
+        obj.update_property(&[gtk::accessible::Property::Label(&label)]);
+        match (*self.focused.borrow()).and_then(|f| items.get(f)) {
+            Some(item) => obj.update_relation(&[gtk::accessible::Relation::ActiveDescendant(
+                item.upcast_ref(),
+            )]),
+            None => obj.reset_relation(gtk::AccessibleRelation::ActiveDescendant),
+        }
+    }
+
+    /// Puts each card's child where the card is drawn, so a screen reader
+    /// can show which card it's reading out.
+    fn place_items(&self, height: i32) {
+        let spread = self.spread(height);
+        let cards = self.cards();
+        let selected = self.selected.borrow();
+        for (i, item) in self.items.borrow().iter().enumerate() {
+            // Children have to be measured before they can be placed.
+            item.measure(gtk::Orientation::Horizontal, -1);
+            let allocation = match (&spread, cards.get(i)) {
+                (Some(spread), Some(card)) => {
+                    let raised = card.card().is_some_and(|c| selected.contains(&c));
+                    let (x, y) = spread.corner(i, raised);
+                    gtk::Allocation::new(
+                        x as i32,
+                        y as i32,
+                        spread.card_width as i32,
+                        spread.card_height as i32,
+                    )
+                }
+                _ => gtk::Allocation::new(0, 0, 0, 0),
+            };
+            item.size_allocate(&allocation, -1);
+        }
+    }
+
+    /// Drops anything selected that is no longer face up in the hand, or
+    /// that can't be chosen any more, and keeps the focus on a card that's
+    /// still there.
+    fn prune_selection(&self) {
+        let count = self.cards().len();
+        let mut focused = self.focused.borrow_mut();
+        if focused.is_some_and(|f| f >= count) {
+            *focused = count.checked_sub(1);
+        }
+        drop(focused);
+        let cards = self
+            .cards()
+            .iter()
+            .filter_map(|card| card.card())
+            .collect::<Vec<Card>>();
+        let mut selected = self.selected.borrow_mut();
+        match *self.choose_count.borrow() {
+            0 => selected.clear(),
+            _ => selected.retain(|card| cards.contains(card)),
         }
     }
 }
@@ -144,28 +678,136 @@ impl ObjectImpl for CardBox {
             obj.as_ref(),
             clone!(@weak self as widget => move |_, cr, w, h| widget.draw(cr, w, h)),
         );
+
+        let click = gtk::GestureClick::new();
+        click.connect_pressed(clone!(@weak self as widget => move |_, _, x, y| {
+            if *widget.selectable.borrow() {
+                widget.obj().grab_focus();
+                widget.click(x, y);
+            }
+        }));
+        obj.add_controller(click);
+
+        let keys = gtk::EventControllerKey::new();
+        keys.connect_key_pressed(
+            clone!(@weak self as widget => @default-return gtk::Inhibit(false), move |_, key, _, _| {
+                gtk::Inhibit(widget.key_pressed(key))
+            }),
+        );
+        obj.add_controller(keys);
+
+        // The focus ring comes and goes with the focus. Coming into the box
+        // with nothing focused yet puts the focus on the first card.
+        obj.connect_has_focus_notify(clone!(@weak self as widget => move |obj| {
+            if obj.has_focus() && widget.focused.borrow().is_none() {
+                widget.move_focus(true);
+            }
+            obj.queue_draw();
+        }));
+
+        // Moving to a screen with a different scale needs the cards drawn
+        // at a different size.
+        obj.connect_scale_factor_notify(|obj| obj.queue_draw());
     }
 
     fn properties() -> &'static [glib::ParamSpec] {
         Self::derived_properties()
     }
 
+    fn signals() -> &'static [Signal] {
+        static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
+        SIGNALS.get_or_init(|| {
+            vec![Signal::builder("cards-chosen")
+                .param_types([BoxedAnyObject::static_type()])
+                .build()]
+        })
+    }
+
     fn set_property(&self, id: usize, value: &glib::Value, pspec: &glib::ParamSpec) {
+        let old = self.cards();
         self.derived_set_property(id, value, pspec);
+        match pspec.name() {
+            "hand" => self.start_motions(&old),
+            "selectable" => self.obj().set_focusable(*self.selectable.borrow()),
+            _ => (),
+        }
+        self.prune_selection();
+        // Anything that changes is going to change what's drawn.
+        self.refresh();
     }
 
     fn property(&self, id: usize, pspec: &glib::ParamSpec) -> glib::Value {
         self.derived_property(id, pspec)
     }
+
+    fn dispose(&self) {
+        for item in self.items.borrow_mut().drain(..) {
+            item.unparent();
+        }
+    }
 }
 
-impl WidgetImpl for CardBox {}
-impl DrawingAreaImpl for CardBox {
-    fn resize(&self, _width: i32, height: i32) {
-        // When resizing the widget, reload the cards and recalculate the
-        // card sub-pixmaps. We want the cards to be proportional with
-        // respect to the widget size.
-        let mut buffer = self.card_buffer.borrow_mut();
-        buffer.create_buffer(height);
+impl WidgetImpl for CardBox {
+    fn size_allocate(&self, width: i32, height: i32, baseline: i32) {
+        self.parent_size_allocate(width, height, baseline);
+        self.place_items(height);
+    }
+}
+// The cards are drawn to fit the widget's height, so a new size means new
+// card images. Drawing them is left until the next draw, which picks them
+// up from the cache if another box already has them at that size.
+impl DrawingAreaImpl for CardBox {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(cards: &str) -> Vec<TableCard> {
+        cards
+            .split(' ')
+            .map(|card| match card {
+                "??" => TableCard::FaceDown,
+                card => TableCard::FaceUp(Card::from(card)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_card_on_top_is_hit() {
+        let spread = Spread {
+            offset: 20.0,
+            card_width: 50.0,
+            card_height: 80.0,
+            raise: 10.0,
+        };
+        let cards = hand("AS 5H ?? KD");
+        let at = |x, y| spread.card_at(&cards, &[], x, y);
+        assert_eq!(Some(0), at(5.0, 50.0));
+        assert_eq!(Some(1), at(25.0, 50.0));
+        assert_eq!(Some(2), at(45.0, 50.0));
+        assert_eq!(Some(3), at(65.0, 50.0));
+        assert_eq!(Some(3), at(105.0, 50.0));
+        assert_eq!(None, at(115.0, 50.0));
+        // Above the cards that aren't raised, and below them.
+        assert_eq!(None, at(5.0, 5.0));
+        assert_eq!(None, at(5.0, 90.0));
+
+        // A selected card is raised, so it's hit higher up.
+        let selected = [Card::from("KD")];
+        assert_eq!(Some(3), spread.card_at(&cards, &selected, 65.0, 5.0));
+        assert_eq!(Some(2), spread.card_at(&cards, &selected, 65.0, 85.0));
+    }
+
+    #[test]
+    fn focus_skips_face_down_cards() {
+        let cards = hand("AS ?? 5H KD ??");
+        assert_eq!(Some(0), next_focus(&cards, None, true));
+        assert_eq!(Some(3), next_focus(&cards, None, false));
+        assert_eq!(Some(2), next_focus(&cards, Some(0), true));
+        assert_eq!(Some(0), next_focus(&cards, Some(2), false));
+        // The focus stops at the ends rather than going round.
+        assert_eq!(Some(3), next_focus(&cards, Some(3), true));
+        assert_eq!(Some(0), next_focus(&cards, Some(0), false));
+        assert_eq!(None, next_focus(&hand("?? ??"), None, true));
     }
 }
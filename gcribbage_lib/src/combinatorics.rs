@@ -34,6 +34,7 @@ where
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::*;
     use crate::deck::Card;
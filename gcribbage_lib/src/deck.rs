@@ -1,5 +1,6 @@
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, SeedableRng};
 use std::cmp::Ordering;
 
 /// Represents the ranks of cards.
@@ -145,6 +146,26 @@ impl Rank {
             _ => 10,
         }
     }
+
+    /// The rank's name in words, such as "five" or "queen".
+    pub fn name(&self) -> &'static str {
+        use Rank::*;
+        match self {
+            Ace => "ace",
+            Two => "two",
+            Three => "three",
+            Four => "four",
+            Five => "five",
+            Six => "six",
+            Seven => "seven",
+            Eight => "eight",
+            Nine => "nine",
+            Ten => "ten",
+            Jack => "jack",
+            Queen => "queen",
+            King => "king",
+        }
+    }
 }
 
 impl Ord for Rank {
@@ -198,6 +219,28 @@ impl Suit {
         }
     }
 
+    /// The letter the suit is written with, as `Suit::from` reads it.
+    pub fn to_char(&self) -> char {
+        use Suit::*;
+        match self {
+            Spades => 'S',
+            Hearts => 'H',
+            Clubs => 'C',
+            Diamonds => 'D',
+        }
+    }
+
+    /// The suit's name in words, such as "hearts".
+    pub fn name(&self) -> &'static str {
+        use Suit::*;
+        match self {
+            Spades => "spades",
+            Hearts => "hearts",
+            Clubs => "clubs",
+            Diamonds => "diamonds",
+        }
+    }
+
     pub fn ordinal(&self) -> usize {
         use Suit::*;
         match self {
@@ -258,6 +301,44 @@ impl Card {
             Rank::from(origin.chars().next().unwrap()),
         )
     }
+
+    /// Reads a card written the way a person might type it, such as "5H",
+    /// "5h", "10h" or "5♥". Anything that isn't a card gives nothing,
+    /// where `Card::from` would panic.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let suit = text.chars().last()?;
+        let rank = &text[..text.len() - suit.len_utf8()];
+        let rank = match rank {
+            "10" => Rank::Ten,
+            _ => {
+                let mut chars = rank.chars();
+                let rank = chars.next()?.to_ascii_uppercase();
+                if chars.next().is_some() {
+                    return None;
+                }
+                Rank::iter().find(|r| r.to_char() == rank)?
+            }
+        };
+        let suit = Suit::iter()
+            .find(|s| s.to_char() == suit.to_ascii_uppercase() || s.to_symbol() == suit)?;
+        Some(Card::new(suit, rank))
+    }
+
+    /// The card's name in words, such as "five of hearts".
+    pub fn name(&self) -> String {
+        format!("{} of {}", self.rank.name(), self.suit.name())
+    }
+
+    /// The card written the way `Card::from` reads it, such as "5H".
+    pub fn notation(&self) -> String {
+        format!("{}{}", self.rank.to_char(), self.suit.to_char())
+    }
+
+    /// The card written short with its suit's symbol, such as "5♥".
+    pub fn short_name(&self) -> String {
+        format!("{}{}", self.rank.to_char(), self.suit.to_symbol())
+    }
 }
 
 impl Ord for Card {
@@ -278,6 +359,8 @@ impl PartialOrd for Card {
 pub struct Deck {
     cards: Vec<Card>,
     stacking: Option<Vec<Card>>,
+    /// Shuffles the deck in the same order every time for the same seed.
+    seeded: Option<StdRng>,
 }
 
 impl Deck {
@@ -288,11 +371,25 @@ impl Deck {
         Self {
             cards,
             stacking: None,
+            seeded: None,
         }
     }
     pub fn stacked(cards: Vec<Card>) -> Self {
         let stacking = Some(cards.to_vec());
-        Self { cards, stacking }
+        Self {
+            cards,
+            stacking,
+            seeded: None,
+        }
+    }
+
+    /// Makes a deck that's shuffled the same way, time after time, as any
+    /// other deck with the same seed.
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            seeded: Some(StdRng::seed_from_u64(seed)),
+            ..Self::new()
+        }
     }
 }
 
@@ -314,7 +411,10 @@ impl Deck {
                 self.cards.extend(
                     Suit::iter().flat_map(|s| Rank::iter().map(move |r| Card { suit: s, rank: r })),
                 );
-                self.cards.shuffle(&mut thread_rng());
+                match &mut self.seeded {
+                    Some(rng) => self.cards.shuffle(rng),
+                    None => self.cards.shuffle(&mut thread_rng()),
+                }
             }
         }
     }
@@ -323,3 +423,74 @@ impl Deck {
         self.cards.drain(0..count).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cards_are_named_in_words() {
+        let tests = vec![
+            ("5H", "five of hearts"),
+            ("AS", "ace of spades"),
+            ("TD", "ten of diamonds"),
+            ("QC", "queen of clubs"),
+        ];
+
+        for (card, expected) in tests {
+            assert_eq!(expected, Card::from(card).name());
+        }
+    }
+
+    #[test]
+    fn cards_are_written_as_they_are_read() {
+        for suit in Suit::iter() {
+            for rank in Rank::iter() {
+                let card = Card::new(suit, rank);
+                assert_eq!(card, Card::from(&card.notation()));
+            }
+        }
+        assert_eq!("TD", Card::from("td").notation());
+    }
+
+    #[test]
+    fn cards_are_named_short() {
+        assert_eq!("5\u{2665}", Card::from("5H").short_name());
+        assert_eq!("T\u{2663}", Card::from("TC").short_name());
+    }
+
+    #[test]
+    fn cards_are_parsed_as_typed() {
+        let tests = vec![
+            ("5H", Some("5H")),
+            ("5h", Some("5H")),
+            (" 10d ", Some("TD")),
+            ("q\u{2660}", Some("QS")),
+            ("1H", None),
+            ("5X", None),
+            ("55H", None),
+            ("H", None),
+            ("", None),
+        ];
+
+        for (text, expected) in tests {
+            assert_eq!(expected.map(Card::from), Card::parse(text), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn seeded_decks_shuffle_alike() {
+        let shuffled = |seed| {
+            let mut deck = Deck::seeded(seed);
+            (0..3)
+                .map(|_| {
+                    deck.shuffle();
+                    deck.deal(6)
+                })
+                .collect::<Vec<Vec<Card>>>()
+        };
+
+        assert_eq!(shuffled(7), shuffled(7));
+        assert_ne!(shuffled(7), shuffled(8));
+    }
+}
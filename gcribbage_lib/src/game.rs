@@ -1,7 +1,9 @@
 use crate::deck::{Card, Deck, Rank};
 use crate::player::{KnowsCribbage, PlayerPosition};
-use crate::scoring::{score_hand, score_pegging, HandScorings, PeggingScorings};
+use crate::rules::{GameRules, Skunk};
+use crate::scoring::{score_crib, score_hand, score_pegging, HandScorings, PeggingScorings};
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 #[derive(Debug, PartialEq)]
 struct ChooseDealer;
@@ -22,7 +24,11 @@ struct SecondGo;
 #[derive(Debug, PartialEq)]
 struct ResumePeggingOrScoreHands;
 #[derive(Debug, PartialEq)]
+struct ScorePone;
+#[derive(Debug, PartialEq)]
 struct ScoreDealer;
+#[derive(Debug, PartialEq)]
+struct ScoreCrib;
 
 enum GameState {
     New(Rule<ChooseDealer>),
@@ -34,38 +40,62 @@ enum GameState {
     AnnouncingFirstGo(Rule<FirstGo>),
     AnnouncingSecondGo(Rule<SecondGo>),
     ScoringLastCard(Rule<ResumePeggingOrScoreHands>),
+    ScoringPone(Rule<ScorePone>),
     ScoringDealer(Rule<ScoreDealer>),
+    ScoringCrib(Rule<ScoreCrib>),
 }
 
 struct GameComponents<'p> {
     deck: Deck,
     hands: HashMap<PlayerPosition, HashSet<Card>>,
     dealt: HashMap<PlayerPosition, HashSet<Card>>,
-    players: HashMap<PlayerPosition, &'p mut dyn KnowsCribbage>,
+    players: HashMap<PlayerPosition, Box<dyn KnowsCribbage + 'p>>,
     scores: HashMap<PlayerPosition, u8>,
     dealer: PlayerPosition,
     crib: HashSet<Card>,
     played: Vec<Card>,
     up_card: Card,
     current_player: PlayerPosition,
+    last_played: PlayerPosition,
+    /// How many more players have said go since the first one did.
+    extra_gos: usize,
+    rules: GameRules,
 }
 
+/// The crib always ends up with this many cards, whatever the variant.
+const CRIB_SIZE: usize = 4;
+
 impl<'p> GameComponents<'p> {
+    #[cfg(test)]
     fn new(first: &'p mut dyn KnowsCribbage, second: &'p mut dyn KnowsCribbage) -> Self {
+        Self::with_players(vec![first, second], GameRules::default())
+    }
+
+    #[cfg(test)]
+    fn with_players(seated: Vec<&'p mut dyn KnowsCribbage>, rules: GameRules) -> Self {
+        Self::seat(seated.into_iter().map(lend).collect(), rules)
+    }
+
+    /// Seats the players given. There has to be one for each seat the rules
+    /// are for.
+    fn seat(seated: Vec<Box<dyn KnowsCribbage + 'p>>, rules: GameRules) -> Self {
+        debug_assert_eq!(rules.players, seated.len());
         let deck = Deck::new();
         let mut hands: HashMap<PlayerPosition, HashSet<Card>> = HashMap::new();
         let mut dealt: HashMap<PlayerPosition, HashSet<Card>> = HashMap::new();
-        let mut players: HashMap<PlayerPosition, &'p mut dyn KnowsCribbage> = HashMap::new();
+        let mut players: HashMap<PlayerPosition, Box<dyn KnowsCribbage + 'p>> = HashMap::new();
         let mut scores: HashMap<PlayerPosition, u8> = HashMap::new();
         let dealer = PlayerPosition::First;
         let crib: HashSet<Card> = HashSet::new();
         let played: Vec<Card> = Vec::new();
         let up_card = Card::from("As");
         let current_player = PlayerPosition::First;
+        let last_played = PlayerPosition::First;
 
-        players.insert(PlayerPosition::First, first);
-        players.insert(PlayerPosition::Second, second);
-        for position in [PlayerPosition::First, PlayerPosition::Second] {
+        for (position, player) in PlayerPosition::iter(rules.players).zip(seated) {
+            players.insert(position, player);
+        }
+        for position in PlayerPosition::iter(rules.players) {
             hands.insert(position, HashSet::new());
             dealt.insert(position, HashSet::new());
             scores.insert(position, 0);
@@ -82,6 +112,60 @@ impl<'p> GameComponents<'p> {
             played,
             up_card,
             current_player,
+            last_played,
+            extra_gos: 0,
+            rules,
+        }
+    }
+
+    fn seats(&self) -> impl Iterator<Item = PlayerPosition> {
+        PlayerPosition::iter(self.rules.players)
+    }
+
+    /// The seat of the player to the left of the given one.
+    fn next_seat(&self, position: PlayerPosition) -> PlayerPosition {
+        position.next(self.rules.players)
+    }
+
+    /// The given player, along with their partner if they have one.
+    fn team(&self, position: PlayerPosition) -> Vec<PlayerPosition> {
+        let mut team = vec![position];
+        team.extend(self.rules.partner(position));
+        team
+    }
+
+    /// Moves the given player's pegs along, returning whether that won
+    /// them the game. Partners share their score, so their pegs move too.
+    fn peg(&mut self, position: PlayerPosition, points: u8) -> bool {
+        let target = self.rules.target_score;
+        let mut won = false;
+        for member in self.team(position) {
+            let score = self.scores.get_mut(&member).unwrap();
+            *score += points;
+            if *score >= target {
+                *score = target;
+                won = true;
+            }
+        }
+        won
+    }
+
+    /// Wraps up the game as won by the given player.
+    fn game_result(&self, winner: PlayerPosition) -> GameResult {
+        let winners = self.team(winner);
+        let loser_score = self
+            .scores
+            .iter()
+            .filter(|(position, _)| !winners.contains(position))
+            .map(|(_, score)| *score)
+            .max()
+            .unwrap_or(0);
+        GameResult {
+            winner,
+            scores: self.scores.clone(),
+            up_card: self.up_card,
+            hands: self.hands.clone(),
+            skunk: self.rules.skunk(loser_score),
         }
     }
 }
@@ -103,18 +187,21 @@ impl From<Rule<ChooseDealer>> for Rule<Deal> {
 
 impl<'p> ApplyRule<'p> for Rule<ChooseDealer> {
     fn apply(self, components: &'p mut GameComponents) -> (GameState, PlayResult) {
-        components.scores.clear();
-        components.scores.insert(PlayerPosition::First, 0);
-        components.scores.insert(PlayerPosition::Second, 0);
+        components.scores = components.seats().map(|p| (p, 0)).collect();
         components.deck.shuffle();
-        let mut cards = components.deck.deal(2);
+        let players = components.rules.players;
+        let mut cards = components.deck.deal(players);
 
-        /* Do not entertain a tie. Just choose new cards. */
-        while cards[0].rank == cards[1].rank {
-            cards = components.deck.deal(2);
+        /* Unless the rules say to break ties by suit, do not entertain a
+         * tie for low card. Just choose new cards. */
+        while components.rules.redeal_on_tied_cut && tied_for_low(&cards) {
+            cards = components.deck.deal(players);
         }
 
-        let dealer_info = DealerInfo::from(&[cards[0], cards[1]]);
+        let dealer_info = DealerInfo::from(&cards[..]);
+        components.dealer = dealer_info.dealer;
+        let pone = components.next_seat(components.dealer);
+        components.peg(pone, components.rules.pone_head_start);
         (
             GameState::ReadyToDeal(Rule::<Deal>::from(self)),
             PlayResult::DealerChosen(dealer_info),
@@ -132,14 +219,20 @@ impl<'p> ApplyRule<'p> for Rule<Deal> {
     fn apply(self, components: &mut GameComponents) -> (GameState, PlayResult) {
         components.deck.shuffle();
         components.hands.clear();
-        components.hands.insert(
-            PlayerPosition::First,
-            HashSet::from_iter(components.deck.deal(6).iter().copied()),
-        );
-        components.hands.insert(
-            PlayerPosition::Second,
-            HashSet::from_iter(components.deck.deal(6).iter().copied()),
-        );
+        for dealt in components.dealt.values_mut() {
+            dealt.clear();
+        }
+        for position in components.seats().collect::<Vec<PlayerPosition>>() {
+            components.hands.insert(
+                position,
+                HashSet::from_iter(components.deck.deal(components.rules.deal_size)),
+            );
+        }
+
+        /* If the discards won't fill the crib, the rest comes from the deck. */
+        let discarded = components.rules.players * components.rules.discards;
+        components.crib =
+            HashSet::from_iter(components.deck.deal(CRIB_SIZE.saturating_sub(discarded)));
         let dealt_info = DealtInfo {
             hands: components.hands.clone(),
             dealer: components.dealer,
@@ -166,11 +259,12 @@ impl From<Rule<TurnUpcard>> for Rule<GameOver> {
 
 impl<'p> ApplyRule<'p> for Rule<ChooseCrib> {
     fn apply(self, components: &mut GameComponents) -> (GameState, PlayResult) {
-        components.crib.clear();
-        for position in [PlayerPosition::First, PlayerPosition::Second] {
+        for position in components.seats().collect::<Vec<PlayerPosition>>() {
             let hand = components.hands.get_mut(&position).unwrap();
             let player = components.players.get_mut(&position).unwrap();
-            let choices = player.choose_crib(hand);
+            let own_crib = position == components.dealer
+                || components.rules.partner(position) == Some(components.dealer);
+            let choices = player.choose_crib(hand, components.rules.discards, own_crib);
             for choice in choices {
                 hand.remove(&choice);
                 components.crib.insert(choice);
@@ -182,6 +276,7 @@ impl<'p> ApplyRule<'p> for Rule<ChooseCrib> {
         }
         let crib_info = CribInfo {
             hands: components.hands.clone(),
+            crib: components.crib.clone(),
             dealer: components.dealer,
             scores: components.scores.clone(),
         };
@@ -246,27 +341,67 @@ impl From<Rule<ResumePeggingOrScoreHands>> for Rule<Pegging> {
     }
 }
 
+impl From<Rule<ResumePeggingOrScoreHands>> for Rule<GameOver> {
+    fn from(_: Rule<ResumePeggingOrScoreHands>) -> Rule<GameOver> {
+        Rule { _state: GameOver }
+    }
+}
+
+impl From<Rule<ResumePeggingOrScoreHands>> for Rule<ScorePone> {
+    fn from(_: Rule<ResumePeggingOrScoreHands>) -> Rule<ScorePone> {
+        Rule { _state: ScorePone }
+    }
+}
+
+impl From<Rule<ScorePone>> for Rule<ScoreDealer> {
+    fn from(_: Rule<ScorePone>) -> Rule<ScoreDealer> {
+        Rule { _state: ScoreDealer }
+    }
+}
+
+impl From<Rule<ScorePone>> for Rule<GameOver> {
+    fn from(_: Rule<ScorePone>) -> Rule<GameOver> {
+        Rule { _state: GameOver }
+    }
+}
+
+impl From<Rule<ScoreDealer>> for Rule<ScoreCrib> {
+    fn from(_: Rule<ScoreDealer>) -> Rule<ScoreCrib> {
+        Rule { _state: ScoreCrib }
+    }
+}
+
+impl From<Rule<ScoreDealer>> for Rule<GameOver> {
+    fn from(_: Rule<ScoreDealer>) -> Rule<GameOver> {
+        Rule { _state: GameOver }
+    }
+}
+
+impl From<Rule<ScoreCrib>> for Rule<Deal> {
+    fn from(_: Rule<ScoreCrib>) -> Rule<Deal> {
+        Rule { _state: Deal }
+    }
+}
+
+impl From<Rule<ScoreCrib>> for Rule<GameOver> {
+    fn from(_: Rule<ScoreCrib>) -> Rule<GameOver> {
+        Rule { _state: GameOver }
+    }
+}
+
 impl<'p> ApplyRule<'p> for Rule<TurnUpcard> {
     fn apply(self, components: &mut GameComponents) -> (GameState, PlayResult) {
-        components.current_player = components.dealer.next();
+        components.current_player = components.next_seat(components.dealer);
         components.played.clear();
         components.up_card = components.deck.deal(1)[0];
-        if components.up_card.rank == Rank::Jack {
-            let score = components.scores.get_mut(&components.dealer).unwrap();
-            *score += 2;
-            if *score >= 121 {
-                *score = 121;
-                let result = GameResult {
-                    up_card: components.up_card,
-                    scores: components.scores.clone(),
-                    hands: components.hands.clone(),
-                    winner: components.dealer,
-                };
-                return (
-                    GameState::GameOver(Rule::<GameOver>::from(self)),
-                    PlayResult::GameOver(result),
-                );
-            }
+        if components.up_card.rank == Rank::Jack
+            && components.peg(components.dealer, components.rules.nibs_value)
+        {
+            let result = components.game_result(components.dealer);
+            return (
+                GameState::GameOver(Rule::<GameOver>::from(self)),
+                PlayResult::GameOver(result),
+            );
         }
         let pegging_info = PeggingInfo {
             hands: components.hands.clone(),
@@ -275,6 +410,7 @@ impl<'p> ApplyRule<'p> for Rule<TurnUpcard> {
             played: components.played.clone(),
             up_card: components.up_card,
             current_player: components.current_player,
+            scorings: Vec::new(),
         };
         (
             GameState::Pegging(Rule::<Pegging>::from(self)),
@@ -302,6 +438,7 @@ impl<'p> ApplyRule<'p> for Rule<Pegging> {
         let player = components.players.get_mut(&components.current_player).unwrap();
         let hand = components.hands.get_mut(&components.current_player).unwrap();
         if must_say_go(hand, &components.played) {
+            components.extra_gos = 0;
             let go_info = GoInfo {
                 dealer: components.dealer,
                 played: components.played.clone(),
@@ -317,31 +454,20 @@ impl<'p> ApplyRule<'p> for Rule<Pegging> {
             );
         }
 
-        let choice = player.play(hand);
+        let choice = player.play(hand, &components.played);
         hand.remove(&choice);
         components.played.push(choice);
-        let found_scorings = score_pegging(components.played.clone());
-        let score = components.scores.get_mut(&components.current_player).unwrap();
-        if !found_scorings.is_empty() {
-            *score += found_scorings
-                .iter()
-                .map(|s| s.value())
-                .sum::<u8>();
-        }
-        if *score >= 121 {
-            *score = 121;
-            let game_result = GameResult {
-                hands: components.hands.clone(),
-                scores: components.scores.clone(),
-                winner: components.current_player,
-                up_card: components.up_card,
-            };
+        components.last_played = components.current_player;
+        let scorings = score_pegging(components.played.clone());
+        let points = scorings.iter().map(|s| s.value()).sum::<u8>();
+        if components.peg(components.current_player, points) {
+            let game_result = components.game_result(components.current_player);
             return (
                 GameState::GameOver(Rule::<GameOver>::from(self)),
                 PlayResult::GameOver(game_result),
             );
         }
-        components.current_player = components.current_player.next();
+        components.current_player = components.next_seat(components.current_player);
         let pegging_info = PeggingInfo {
             hands: components.hands.clone(),
             dealer: components.dealer,
@@ -349,18 +475,18 @@ impl<'p> ApplyRule<'p> for Rule<Pegging> {
             played: components.played.clone(),
             up_card: components.up_card,
             current_player: components.current_player,
+            scorings,
         };
         (
             GameState::Pegging(self),
             PlayResult::WaitingForPlay(pegging_info),
         )
-        
     }
 }
 
 impl<'p> ApplyRule<'p> for Rule<FirstGo> {
     fn apply(self, components: &mut GameComponents) -> (GameState, PlayResult) {
-        components.current_player = components.current_player.next();
+        components.current_player = components.next_seat(components.current_player);
         let hand = components.hands.get(&components.current_player).unwrap();
         if must_say_go(hand, &components.played) {
             let go_info = GoInfo {
@@ -372,6 +498,15 @@ impl<'p> ApplyRule<'p> for Rule<FirstGo> {
                 up_card: components.up_card,
             };
 
+            /* The go keeps passing around the table until somebody can
+             * play, or everybody has said go. */
+            components.extra_gos += 1;
+            if components.extra_gos + 1 < components.rules.players {
+                return (GameState::AnnouncingFirstGo(self), PlayResult::Go(go_info));
+            }
+
+            /* Nobody can play, so whoever played the last card scores it. */
+            components.current_player = components.last_played;
             return (
                 GameState::AnnouncingSecondGo(Rule::<SecondGo>::from(self)),
                 PlayResult::Go(go_info),
@@ -384,6 +519,7 @@ impl<'p> ApplyRule<'p> for Rule<FirstGo> {
             played: components.played.clone(),
             up_card: components.up_card,
             current_player: components.current_player,
+            scorings: Vec::new(),
         };
         (
             GameState::Pegging(Rule::<Pegging>::from(self)),
@@ -394,23 +530,23 @@ impl<'p> ApplyRule<'p> for Rule<FirstGo> {
 
 impl<'p> ApplyRule<'p> for Rule<SecondGo> {
     fn apply(self, components: &mut GameComponents) -> (GameState, PlayResult) {
-        let score = components.scores.get_mut(&components.current_player).unwrap();
-        *score += 2;
-        if *score >= 121 {
-            *score = 121;
-            let result = GameResult {
-                up_card: components.up_card,
-                scores: components.scores.clone(),
-                hands: components.hands.clone(),
-                winner: components.current_player,
-            };
+        /* The last card pegs one, unless it made 31 and has already
+         * scored for that. */
+        let count = components.played.iter().map(|c| c.rank.value()).sum::<u8>();
+        let scorings = match count {
+            31 => Vec::new(),
+            _ => vec![PeggingScorings::LastCard],
+        };
+        let points = scorings.iter().map(|s| s.value()).sum::<u8>();
+        if components.peg(components.current_player, points) {
+            let result = components.game_result(components.current_player);
             return (
                 GameState::GameOver(Rule::<GameOver>::from(self)),
                 PlayResult::GameOver(result),
             );
         }
 
-        components.current_player = components.current_player.next();
+        components.current_player = components.next_seat(components.current_player);
         let pegging_info = PeggingInfo {
             hands: components.hands.clone(),
             dealer: components.dealer,
@@ -418,6 +554,7 @@ impl<'p> ApplyRule<'p> for Rule<SecondGo> {
             played: components.played.clone(),
             up_card: components.up_card,
             current_player: components.current_player,
+            scorings,
         };
         (
             GameState::ScoringLastCard(Rule::<ResumePeggingOrScoreHands>::from(self)),
@@ -429,38 +566,46 @@ impl<'p> ApplyRule<'p> for Rule<SecondGo> {
 impl<'p> ApplyRule<'p> for Rule<ResumePeggingOrScoreHands> {
     fn apply(self, components: &mut GameComponents) -> (GameState, PlayResult) {
         components.played = Vec::new();
-        if components.hands.get(&components.current_player).unwrap().is_empty() {
-            /* Current player can't play. Skip them. */
-            components.current_player = components.current_player.next();
-            if components.hands.get(&components.current_player).unwrap().is_empty() {
-                /* Neither player can play. Move to start counting hands. */
-                let dealt = components.dealt.get(&components.dealer.next()).unwrap();
-                let scorings = score_hand(dealt, components.up_card);
-                let score = components.scores.get_mut(&components.dealer.next()).unwrap();
-                *score += scorings
-                    .iter()
-                    .map(|x| x.value())
-                    .sum::<u8>();
-                if *score >= 121 {
-                    let game_results = GameResult {
-                        hands: components.hands.clone(),
-                        scores: components.scores.clone(),
-                        up_card: components.up_card,
-                        winner: components.current_player,
-                    };
-                } else {
-                    let scoring_info = ScoreInfo {
-                        hand: dealt.clone(),
-                        player: components.dealer.next(),
-                        scores: components.scores.clone(),
-                        scorings: Some(scorings),
-                        up_card: components.up_card,
-                    };
+
+        /* Players with no cards left can't lead. Skip them. */
+        let leader = std::iter::successors(Some(components.current_player), |p| {
+            Some(components.next_seat(*p))
+        })
+        .take(components.rules.players)
+        .find(|p| !components.hands.get(p).unwrap().is_empty());
+
+        match leader {
+            Some(leader) => components.current_player = leader,
+            None => {
+                /* Nobody can play. Move to start counting hands, starting
+                 * to the left of the dealer. */
+                let pone = components.next_seat(components.dealer);
+                components.current_player = pone;
+                let scorings = score_hand(components.dealt.get(&pone).unwrap(), components.up_card);
+                if components.peg(pone, scorings.iter().map(|x| x.value()).sum::<u8>()) {
+                    let game_result = components.game_result(pone);
+                    return (
+                        GameState::GameOver(Rule::<GameOver>::from(self)),
+                        PlayResult::GameOver(game_result),
+                    );
+                }
+                let scoring_info = ScoreInfo {
+                    hand: components.dealt.get(&pone).unwrap().clone(),
+                    player: pone,
+                    scores: components.scores.clone(),
+                    scorings: Some(scorings),
+                    up_card: components.up_card,
+                };
+                if components.next_seat(pone) != components.dealer {
                     return (
-                        GameState::ScoringDealer(Rule::<ScoreDealer>::from(self)),
+                        GameState::ScoringPone(Rule::<ScorePone>::from(self)),
                         PlayResult::HandScore(scoring_info),
                     );
                 }
+                return (
+                    GameState::ScoringDealer(Rule::<ScoreDealer>::from(self)),
+                    PlayResult::HandScore(scoring_info),
+                );
             }
         }
         let pegging_info = PeggingInfo {
@@ -470,6 +615,7 @@ impl<'p> ApplyRule<'p> for Rule<ResumePeggingOrScoreHands> {
             played: components.played.clone(),
             scores: components.scores.clone(),
             up_card: components.up_card,
+            scorings: Vec::new(),
         };
         (
             GameState::Pegging(Rule::<Pegging>::from(self)),
@@ -478,6 +624,94 @@ impl<'p> ApplyRule<'p> for Rule<ResumePeggingOrScoreHands> {
     }
 }
 
+impl<'p> ApplyRule<'p> for Rule<ScorePone> {
+    fn apply(self, components: &mut GameComponents) -> (GameState, PlayResult) {
+        let position = components.next_seat(components.current_player);
+        components.current_player = position;
+        let scorings = score_hand(components.dealt.get(&position).unwrap(), components.up_card);
+        if components.peg(position, scorings.iter().map(|x| x.value()).sum::<u8>()) {
+            let game_result = components.game_result(position);
+            return (
+                GameState::GameOver(Rule::<GameOver>::from(self)),
+                PlayResult::GameOver(game_result),
+            );
+        }
+        let scoring_info = ScoreInfo {
+            hand: components.dealt.get(&position).unwrap().clone(),
+            player: position,
+            scores: components.scores.clone(),
+            scorings: Some(scorings),
+            up_card: components.up_card,
+        };
+        if components.next_seat(position) != components.dealer {
+            return (
+                GameState::ScoringPone(self),
+                PlayResult::HandScore(scoring_info),
+            );
+        }
+        (
+            GameState::ScoringDealer(Rule::<ScoreDealer>::from(self)),
+            PlayResult::HandScore(scoring_info),
+        )
+    }
+}
+
+impl<'p> ApplyRule<'p> for Rule<ScoreDealer> {
+    fn apply(self, components: &mut GameComponents) -> (GameState, PlayResult) {
+        let dealer = components.dealer;
+        let scorings = score_hand(components.dealt.get(&dealer).unwrap(), components.up_card);
+        if components.peg(dealer, scorings.iter().map(|x| x.value()).sum::<u8>()) {
+            let game_result = components.game_result(dealer);
+            return (
+                GameState::GameOver(Rule::<GameOver>::from(self)),
+                PlayResult::GameOver(game_result),
+            );
+        }
+        let scoring_info = ScoreInfo {
+            hand: components.dealt.get(&dealer).unwrap().clone(),
+            player: dealer,
+            scores: components.scores.clone(),
+            scorings: Some(scorings),
+            up_card: components.up_card,
+        };
+        (
+            GameState::ScoringCrib(Rule::<ScoreCrib>::from(self)),
+            PlayResult::HandScore(scoring_info),
+        )
+    }
+}
+
+impl<'p> ApplyRule<'p> for Rule<ScoreCrib> {
+    fn apply(self, components: &mut GameComponents) -> (GameState, PlayResult) {
+        let dealer = components.dealer;
+        let scorings = match components.rules.crib_four_card_flush {
+            true => score_hand(&components.crib, components.up_card),
+            false => score_crib(&components.crib, components.up_card),
+        };
+        if components.peg(dealer, scorings.iter().map(|x| x.value()).sum::<u8>()) {
+            let game_result = components.game_result(dealer);
+            return (
+                GameState::GameOver(Rule::<GameOver>::from(self)),
+                PlayResult::GameOver(game_result),
+            );
+        }
+        let scoring_info = ScoreInfo {
+            hand: components.crib.clone(),
+            player: dealer,
+            scores: components.scores.clone(),
+            scorings: Some(scorings),
+            up_card: components.up_card,
+        };
+
+        /* The deal passes to the left for the next hand. */
+        components.dealer = components.next_seat(dealer);
+        (
+            GameState::ReadyToDeal(Rule::<Deal>::from(self)),
+            PlayResult::HandScore(scoring_info),
+        )
+    }
+}
+
 pub struct DealerInfo {
     pub dealer: PlayerPosition,
     pub chosen_cards: HashMap<PlayerPosition, Card>,
@@ -491,6 +725,7 @@ pub struct DealtInfo {
 
 pub struct CribInfo {
     pub hands: HashMap<PlayerPosition, HashSet<Card>>,
+    pub crib: HashSet<Card>,
     pub dealer: PlayerPosition,
     pub scores: HashMap<PlayerPosition, u8>,
 }
@@ -502,6 +737,8 @@ pub struct PeggingInfo {
     pub up_card: Card,
     pub played: Vec<Card>,
     pub current_player: PlayerPosition,
+    /// What the last card played scored, if it was just played.
+    pub scorings: Vec<PeggingScorings>,
 }
 
 pub struct GoInfo {
@@ -514,10 +751,13 @@ pub struct GoInfo {
 }
 
 pub struct GameResult {
+    /// The player who pegged out. In a partnership game, their partner has
+    /// won as well.
     pub winner: PlayerPosition,
     pub scores: HashMap<PlayerPosition, u8>,
     pub up_card: Card,
     pub hands: HashMap<PlayerPosition, HashSet<Card>>,
+    pub skunk: Option<Skunk>,
 }
 
 pub struct ScoreInfo {
@@ -528,15 +768,25 @@ pub struct ScoreInfo {
     pub scores: HashMap<PlayerPosition, u8>,
 }
 
-impl From<&[Card; 2]> for DealerInfo {
-    fn from(cards: &[Card; 2]) -> DealerInfo {
-        let dealer = match cards[0].rank < cards[1].rank {
-            true => PlayerPosition::First,
-            false => PlayerPosition::Second,
-        };
-        let mut chosen_cards: HashMap<PlayerPosition, Card> = HashMap::new();
-        chosen_cards.insert(PlayerPosition::First, cards[0]);
-        chosen_cards.insert(PlayerPosition::Second, cards[1]);
+/// Whether more than one player cut the lowest rank.
+fn tied_for_low(cards: &[Card]) -> bool {
+    let low = cards.iter().map(|c| c.rank).min().unwrap();
+    cards.iter().filter(|c| c.rank == low).count() > 1
+}
+
+impl From<&[Card]> for DealerInfo {
+    /// Each player cuts a card, in seat order. The lowest card deals.
+    fn from(cards: &[Card]) -> DealerInfo {
+        let chosen_cards: HashMap<PlayerPosition, Card> = PlayerPosition::iter(cards.len())
+            .zip(cards.iter().copied())
+            .collect();
+
+        /* Cards compare by rank first, so suit only matters on a tie. */
+        let dealer = *chosen_cards
+            .iter()
+            .min_by_key(|(_, card)| **card)
+            .unwrap()
+            .0;
         DealerInfo {
             dealer,
             chosen_cards,
@@ -556,18 +806,134 @@ pub enum PlayResult {
     Incomplete,
 }
 
+/// Why a game couldn't be set up.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum SeatingError {
+    /// The rules are for a different number of players than were seated.
+    WrongNumberOfPlayers { rules: usize, seated: usize },
+}
+
+impl fmt::Display for SeatingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeatingError::WrongNumberOfPlayers { rules, seated } => write!(
+                f,
+                "The rules are for {} players, but {} were seated",
+                rules, seated
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SeatingError {}
+
+/// Lends a player to a game.
+fn lend<'p>(player: &'p mut dyn KnowsCribbage) -> Box<dyn KnowsCribbage + 'p> {
+    Box::new(player)
+}
+
 pub struct GameRunner<'p> {
     _game_state: GameState,
     _game_components: GameComponents<'p>,
 }
 
 impl<'a> GameRunner<'a> {
-    pub fn new(first: &'a mut dyn KnowsCribbage, second: &'a mut dyn KnowsCribbage) -> Self {
+    /// Sets up a game between two players. The rules have to be for two
+    /// players.
+    pub fn new(
+        first: &'a mut dyn KnowsCribbage,
+        second: &'a mut dyn KnowsCribbage,
+        rules: GameRules,
+    ) -> Result<Self, SeatingError> {
+        Self::with_players(vec![first, second], rules)
+    }
+
+    /// Sets up a game for however many players the rules are for, seated
+    /// in the order given.
+    pub fn with_players(
+        players: Vec<&'a mut dyn KnowsCribbage>,
+        rules: GameRules,
+    ) -> Result<Self, SeatingError> {
+        Self::owning(players.into_iter().map(lend).collect(), rules)
+    }
+
+    /// Sets up a game that has its players to itself, so it can be kept for
+    /// as long as it's wanted without anything else having to outlast it.
+    pub fn owning(
+        players: Vec<Box<dyn KnowsCribbage + 'a>>,
+        rules: GameRules,
+    ) -> Result<Self, SeatingError> {
+        if players.len() != rules.players {
+            return Err(SeatingError::WrongNumberOfPlayers {
+                rules: rules.players,
+                seated: players.len(),
+            });
+        }
         let _game_state = GameState::New(Rule { _state: ChooseDealer });
-        let _game_components = GameComponents::new(first, second);
-        Self {
+        let _game_components = GameComponents::seat(players, rules);
+        Ok(Self {
             _game_state,
             _game_components,
+        })
+    }
+
+    pub fn rules(&self) -> GameRules {
+        self._game_components.rules
+    }
+
+    /// Skips cutting for the deal and has the given player deal first, as
+    /// when the loser of one game in a match deals the next. This has to be
+    /// done before the game has started.
+    pub fn set_first_dealer(&mut self, dealer: PlayerPosition) {
+        let components = &mut self._game_components;
+        components.dealer = dealer;
+        let pone = components.next_seat(dealer);
+        components.peg(pone, components.rules.pone_head_start);
+        self._game_state = GameState::ReadyToDeal(Rule { _state: Deal });
+    }
+
+    /// Shuffles with a deck seeded with the given number, so that a game
+    /// with the same seed is dealt the same cards. This has to be done
+    /// before the game has started.
+    pub fn set_seed(&mut self, seed: u64) {
+        self._game_components.deck = Deck::seeded(seed);
+    }
+
+    /// Applies the rule for the current state of the game, moving the game
+    /// along to its next state.
+    ///
+    /// Once the game is over there is nothing left to play, and every
+    /// further step results in `PlayResult::Incomplete`.
+    pub fn step(&mut self) -> PlayResult {
+        let state = std::mem::replace(
+            &mut self._game_state,
+            GameState::GameOver(Rule { _state: GameOver }),
+        );
+        let components = &mut self._game_components;
+        let (next_state, result) = match state {
+            GameState::New(rule) => rule.apply(components),
+            GameState::ReadyToDeal(rule) => rule.apply(components),
+            GameState::WaitingForCrib(rule) => rule.apply(components),
+            GameState::TurningUpCard(rule) => rule.apply(components),
+            GameState::Pegging(rule) => rule.apply(components),
+            GameState::AnnouncingFirstGo(rule) => rule.apply(components),
+            GameState::AnnouncingSecondGo(rule) => rule.apply(components),
+            GameState::ScoringLastCard(rule) => rule.apply(components),
+            GameState::ScoringPone(rule) => rule.apply(components),
+            GameState::ScoringDealer(rule) => rule.apply(components),
+            GameState::ScoringCrib(rule) => rule.apply(components),
+            GameState::GameOver(rule) => (GameState::GameOver(rule), PlayResult::Incomplete),
+        };
+        self._game_state = next_state;
+        result
+    }
+
+    /// Plays the game until it is over.
+    pub fn play_to_end(&mut self) -> GameResult {
+        loop {
+            if let PlayResult::GameOver(result) = self.step() {
+                return result;
+            }
         }
     }
 }
@@ -717,6 +1083,15 @@ mod test_rules {
         }
         match result {
             PlayResult::CribChosen(result) => {
+                assert_eq!(
+                    HashSet::from_iter(vec![
+                        Card::from("As"),
+                        Card::from("2s"),
+                        Card::from("Ah"),
+                        Card::from("2h"),
+                    ]),
+                    result.crib
+                );
                 assert_eq!(
                     HashSet::from_iter(vec![
                         Card::from("3s"),
@@ -890,6 +1265,10 @@ mod test_rules {
                 assert_eq!(0, *result.scores.get(&PlayerPosition::Second).unwrap());
                 assert_eq!(PlayerPosition::Second, result.current_player);
                 assert!(!result.hands.get(&PlayerPosition::First).unwrap().contains(&Card::from("As")));
+                assert_eq!(
+                    vec![PeggingScorings::Fifteen, PeggingScorings::RunOfThree],
+                    result.scorings
+                );
             }
             _ => panic!("Wrong rule result"),
         }
@@ -1058,7 +1437,7 @@ mod test_rules {
         components.current_player = PlayerPosition::First;
         components.scores.insert(
             PlayerPosition::First,
-            119,
+            120,
         );
         components.hands.insert(
             PlayerPosition::First,
@@ -1143,6 +1522,641 @@ mod test_rules {
         }
     }
 
+    #[test]
+    fn tied_cut_broken_by_suit() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::new(&mut first, &mut second);
+        components.rules.redeal_on_tied_cut = false;
+        components.deck = Deck::stacked(vec![Card::from("Kd"), Card::from("Ks")]);
+        let rule = Rule { _state: ChooseDealer };
+        let (_, play_result) = rule.apply(&mut components);
+        match play_result {
+            PlayResult::DealerChosen(result) => {
+                assert_eq!(PlayerPosition::Second, result.dealer);
+                assert_eq!(PlayerPosition::Second, components.dealer);
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn up_card_nibs_house_value() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::new(&mut first, &mut second);
+        components.rules.nibs_value = 1;
+        components.deck = Deck::stacked(vec![Card::from("Jd")]);
+        components.dealer = PlayerPosition::First;
+        let rule = Rule { _state: TurnUpcard };
+        let (_, result) = rule.apply(&mut components);
+        match result {
+            PlayResult::WaitingForPlay(result) => {
+                assert_eq!(1, *result.scores.get(&PlayerPosition::First).unwrap());
+                assert_eq!(0, *result.scores.get(&PlayerPosition::Second).unwrap());
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn can_win_short_game_from_pegging() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::new(&mut first, &mut second);
+        components.rules = GameRules::short_game();
+        let rule = Rule { _state: Pegging };
+        components.current_player = PlayerPosition::First;
+        components.hands.insert(
+            PlayerPosition::First,
+            HashSet::from_iter(vec![Card::from("6s")]),
+        );
+        components.played = vec![Card::from("4c"), Card::from("5c")];
+        components.scores.insert(PlayerPosition::First, 58);
+        components.scores.insert(PlayerPosition::Second, 20);
+
+        let (next_state, result) = rule.apply(&mut components);
+        match next_state {
+            GameState::GameOver(next_rule) => assert_eq!(GameOver, next_rule._state),
+            _ => panic!("Wrong game state"),
+        }
+        match result {
+            PlayResult::GameOver(result) => {
+                assert_eq!(61, *result.scores.get(&PlayerPosition::First).unwrap());
+                assert_eq!(PlayerPosition::First, result.winner);
+                assert_eq!(Some(Skunk::Single), result.skunk);
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn counts_dealer_after_pone() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::new(&mut first, &mut second);
+        let rule = Rule { _state: ScoreDealer };
+        components.dealer = PlayerPosition::First;
+        components.dealt.insert(
+            PlayerPosition::First,
+            HashSet::from_iter(vec![
+                Card::from("4s"),
+                Card::from("5h"),
+                Card::from("6d"),
+                Card::from("8c"),
+            ]),
+        );
+        components.up_card = Card::from("2s");
+
+        let (next_state, result) = rule.apply(&mut components);
+        match next_state {
+            GameState::ScoringCrib(next_rule) => assert_eq!(ScoreCrib, next_rule._state),
+            _ => panic!("Wrong game state"),
+        }
+        match result {
+            PlayResult::HandScore(result) => {
+                assert_eq!(PlayerPosition::First, result.player);
+                assert_eq!(7, *result.scores.get(&PlayerPosition::First).unwrap());
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn crib_four_card_flush() {
+        for (allowed, expected) in [(false, 0), (true, 4)] {
+            let mut first: SimplePlayer = SimplePlayer {};
+            let mut second: SimplePlayer = SimplePlayer {};
+            let mut components = GameComponents::new(&mut first, &mut second);
+            components.rules.crib_four_card_flush = allowed;
+            let rule = Rule { _state: ScoreCrib };
+            components.dealer = PlayerPosition::Second;
+            components.crib = HashSet::from_iter(vec![
+                Card::from("As"),
+                Card::from("3s"),
+                Card::from("7s"),
+                Card::from("9s"),
+            ]);
+            components.up_card = Card::from("Qd");
+
+            let (next_state, result) = rule.apply(&mut components);
+            match next_state {
+                GameState::ReadyToDeal(next_rule) => assert_eq!(Deal, next_rule._state),
+                _ => panic!("Wrong game state"),
+            }
+            match result {
+                PlayResult::HandScore(result) => {
+                    assert_eq!(PlayerPosition::Second, result.player);
+                    assert_eq!(
+                        expected,
+                        *result.scores.get(&PlayerPosition::Second).unwrap()
+                    );
+                }
+                _ => panic!("Wrong rule result"),
+            }
+            assert_eq!(PlayerPosition::First, components.dealer);
+        }
+    }
+
+    #[test]
+    fn plays_a_whole_game() {
+        for rules in [GameRules::new(), GameRules::short_game()] {
+            let mut first: SimplePlayer = SimplePlayer {};
+            let mut second: SimplePlayer = SimplePlayer {};
+            let mut runner = GameRunner::new(&mut first, &mut second, rules).unwrap();
+            let result = runner.play_to_end();
+            assert_eq!(
+                rules.target_score,
+                *result.scores.get(&result.winner).unwrap()
+            );
+            assert!(matches!(runner.step(), PlayResult::Incomplete));
+        }
+    }
+
+    #[test]
+    fn three_players_cut_for_deal() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut third: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::with_players(
+            vec![&mut first, &mut second, &mut third],
+            GameRules::three_player(),
+        );
+        components.deck = Deck::stacked(vec![
+            Card::from("4d"),
+            Card::from("Kc"),
+            Card::from("4s"),
+            Card::from("9d"),
+            Card::from("3c"),
+            Card::from("4s"),
+        ]);
+        let rule = Rule { _state: ChooseDealer };
+        let (_, play_result) = rule.apply(&mut components);
+        match play_result {
+            PlayResult::DealerChosen(result) => {
+                assert_eq!(PlayerPosition::Second, result.dealer);
+                assert_eq!(
+                    Card::from("3c"),
+                    *result.chosen_cards.get(&PlayerPosition::Second).unwrap()
+                );
+                assert_eq!(3, components.scores.len());
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn deals_three_player_hands_and_crib_card() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut third: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::with_players(
+            vec![&mut first, &mut second, &mut third],
+            GameRules::three_player(),
+        );
+        let cards = vec![
+            Card::from("As"),
+            Card::from("2s"),
+            Card::from("3s"),
+            Card::from("4s"),
+            Card::from("5s"),
+            Card::from("Ah"),
+            Card::from("2h"),
+            Card::from("3h"),
+            Card::from("4h"),
+            Card::from("5h"),
+            Card::from("Ad"),
+            Card::from("2d"),
+            Card::from("3d"),
+            Card::from("4d"),
+            Card::from("5d"),
+            Card::from("Kc"),
+        ];
+        components.deck = Deck::stacked(cards.clone());
+        let rule = Rule { _state: Deal };
+        let (_, result) = rule.apply(&mut components);
+        match result {
+            PlayResult::CardsDealt(result) => {
+                assert_eq!(
+                    *result.hands.get(&PlayerPosition::Third).unwrap(),
+                    HashSet::from_iter(cards[10..15].iter().cloned())
+                );
+            }
+            _ => panic!("Wrong rule result"),
+        }
+        assert_eq!(HashSet::from_iter(vec![Card::from("Kc")]), components.crib);
+
+        let rule = Rule { _state: ChooseCrib };
+        let (_, result) = rule.apply(&mut components);
+        match result {
+            PlayResult::CribChosen(result) => {
+                for position in PlayerPosition::iter(3) {
+                    assert_eq!(4, result.hands.get(&position).unwrap().len());
+                }
+            }
+            _ => panic!("Wrong rule result"),
+        }
+        assert_eq!(
+            HashSet::from_iter(vec![
+                Card::from("Kc"),
+                Card::from("As"),
+                Card::from("Ah"),
+                Card::from("Ad"),
+            ]),
+            components.crib
+        );
+    }
+
+    #[test]
+    fn go_passes_around_three_players() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut third: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::with_players(
+            vec![&mut first, &mut second, &mut third],
+            GameRules::three_player(),
+        );
+        components.current_player = PlayerPosition::Second;
+        components.last_played = PlayerPosition::First;
+        components.played = vec![Card::from("Kc"), Card::from("Qd"), Card::from("7h")];
+        components.hands.insert(
+            PlayerPosition::First,
+            HashSet::from_iter(vec![Card::from("Ts")]),
+        );
+        components.hands.insert(
+            PlayerPosition::Second,
+            HashSet::from_iter(vec![Card::from("9s")]),
+        );
+        components.hands.insert(
+            PlayerPosition::Third,
+            HashSet::from_iter(vec![Card::from("8s")]),
+        );
+
+        let rule = Rule { _state: Pegging };
+        let (next_state, result) = rule.apply(&mut components);
+        let rule = match next_state {
+            GameState::AnnouncingFirstGo(next_rule) => next_rule,
+            _ => panic!("Wrong game state"),
+        };
+        match result {
+            PlayResult::Go(result) => assert_eq!(PlayerPosition::Second, result.caller),
+            _ => panic!("Wrong rule result"),
+        }
+
+        let (next_state, result) = rule.apply(&mut components);
+        let rule = match next_state {
+            GameState::AnnouncingFirstGo(next_rule) => next_rule,
+            _ => panic!("Wrong game state"),
+        };
+        match result {
+            PlayResult::Go(result) => assert_eq!(PlayerPosition::Third, result.caller),
+            _ => panic!("Wrong rule result"),
+        }
+
+        let (next_state, result) = rule.apply(&mut components);
+        let rule = match next_state {
+            GameState::AnnouncingSecondGo(next_rule) => next_rule,
+            _ => panic!("Wrong game state"),
+        };
+        match result {
+            PlayResult::Go(result) => assert_eq!(PlayerPosition::First, result.caller),
+            _ => panic!("Wrong rule result"),
+        }
+
+        let (_, result) = rule.apply(&mut components);
+        match result {
+            PlayResult::WaitingForLastCardAcknowledgement(result) => {
+                assert_eq!(1, *result.scores.get(&PlayerPosition::First).unwrap());
+                assert_eq!(vec![PeggingScorings::LastCard], result.scorings);
+                assert_eq!(PlayerPosition::Second, result.current_player);
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn thirty_one_scores_only_for_the_31() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::new(&mut first, &mut second);
+        components.current_player = PlayerPosition::First;
+        components.played = vec![Card::from("Kc"), Card::from("Qd"), Card::from("Ah")];
+        components.hands.insert(
+            PlayerPosition::First,
+            HashSet::from_iter(vec![Card::from("Ts")]),
+        );
+        components.hands.insert(
+            PlayerPosition::Second,
+            HashSet::from_iter(vec![Card::from("9s")]),
+        );
+
+        let rule = Rule { _state: Pegging };
+        let (next_state, result) = rule.apply(&mut components);
+        match result {
+            PlayResult::WaitingForPlay(result) => {
+                assert_eq!(vec![PeggingScorings::ThirtyOne], result.scorings);
+                assert_eq!(2, *result.scores.get(&PlayerPosition::First).unwrap());
+            }
+            _ => panic!("Wrong rule result"),
+        }
+        let rule = match next_state {
+            GameState::Pegging(next_rule) => next_rule,
+            _ => panic!("Wrong game state"),
+        };
+        let (next_state, _) = rule.apply(&mut components);
+        let rule = match next_state {
+            GameState::AnnouncingFirstGo(next_rule) => next_rule,
+            _ => panic!("Wrong game state"),
+        };
+        let (next_state, _) = rule.apply(&mut components);
+        let rule = match next_state {
+            GameState::AnnouncingSecondGo(next_rule) => next_rule,
+            _ => panic!("Wrong game state"),
+        };
+        let (_, result) = rule.apply(&mut components);
+        match result {
+            PlayResult::WaitingForLastCardAcknowledgement(result) => {
+                assert!(result.scorings.is_empty());
+                assert_eq!(2, *result.scores.get(&PlayerPosition::First).unwrap());
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn go_lets_last_player_keep_playing() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut third: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::with_players(
+            vec![&mut first, &mut second, &mut third],
+            GameRules::three_player(),
+        );
+        components.current_player = PlayerPosition::Third;
+        components.last_played = PlayerPosition::Second;
+        components.played = vec![Card::from("Kc"), Card::from("Qd"), Card::from("5c")];
+        components.hands.insert(
+            PlayerPosition::First,
+            HashSet::from_iter(vec![Card::from("Ts")]),
+        );
+        components.hands.insert(
+            PlayerPosition::Second,
+            HashSet::from_iter(vec![Card::from("Ah")]),
+        );
+        components.hands.insert(
+            PlayerPosition::Third,
+            HashSet::from_iter(vec![Card::from("Js")]),
+        );
+
+        /* Third and First can't play, but Second still can. */
+        let rule = Rule { _state: Pegging };
+        let (next_state, _) = rule.apply(&mut components);
+        let rule = match next_state {
+            GameState::AnnouncingFirstGo(next_rule) => next_rule,
+            _ => panic!("Wrong game state"),
+        };
+        let (next_state, result) = rule.apply(&mut components);
+        let rule = match next_state {
+            GameState::AnnouncingFirstGo(next_rule) => next_rule,
+            _ => panic!("Wrong game state"),
+        };
+        match result {
+            PlayResult::Go(result) => assert_eq!(PlayerPosition::First, result.caller),
+            _ => panic!("Wrong rule result"),
+        }
+        let (next_state, result) = rule.apply(&mut components);
+        match next_state {
+            GameState::Pegging(next_rule) => assert_eq!(Pegging, next_rule._state),
+            _ => panic!("Wrong game state"),
+        }
+        match result {
+            PlayResult::WaitingForPlay(result) => {
+                assert_eq!(PlayerPosition::Second, result.current_player);
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn counts_three_hands_left_of_dealer() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut third: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::with_players(
+            vec![&mut first, &mut second, &mut third],
+            GameRules::three_player(),
+        );
+        components.dealer = PlayerPosition::Second;
+        components.up_card = Card::from("Kd");
+        for position in PlayerPosition::iter(3) {
+            components.dealt.insert(
+                position,
+                HashSet::from_iter(vec![
+                    Card::from("As"),
+                    Card::from("3h"),
+                    Card::from("7c"),
+                    Card::from("9d"),
+                ]),
+            );
+        }
+
+        let rule = Rule { _state: ResumePeggingOrScoreHands };
+        let (next_state, result) = rule.apply(&mut components);
+        let rule = match next_state {
+            GameState::ScoringPone(next_rule) => next_rule,
+            _ => panic!("Wrong game state"),
+        };
+        match result {
+            PlayResult::HandScore(result) => assert_eq!(PlayerPosition::Third, result.player),
+            _ => panic!("Wrong rule result"),
+        }
+
+        let (next_state, result) = rule.apply(&mut components);
+        match next_state {
+            GameState::ScoringDealer(next_rule) => assert_eq!(ScoreDealer, next_rule._state),
+            _ => panic!("Wrong game state"),
+        }
+        match result {
+            PlayResult::HandScore(result) => assert_eq!(PlayerPosition::First, result.player),
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn plays_a_whole_three_player_game() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut third: SimplePlayer = SimplePlayer {};
+        let mut runner = GameRunner::with_players(
+            vec![&mut first, &mut second, &mut third],
+            GameRules::three_player(),
+        )
+        .unwrap();
+        let result = runner.play_to_end();
+        assert_eq!(121, *result.scores.get(&result.winner).unwrap());
+        assert_eq!(3, result.scores.len());
+    }
+
+    #[test]
+    fn seats_one_player_per_seat() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let result = GameRunner::new(&mut first, &mut second, GameRules::three_player());
+        assert_eq!(
+            Some(SeatingError::WrongNumberOfPlayers {
+                rules: 3,
+                seated: 2
+            }),
+            result.err()
+        );
+    }
+
+    #[test]
+    fn games_can_own_their_players() {
+        let players: Vec<Box<dyn KnowsCribbage>> =
+            vec![Box::new(SimplePlayer {}), Box::new(SimplePlayer {})];
+        let mut runner = GameRunner::owning(players, GameRules::new()).unwrap();
+        runner.set_seed(3);
+        let result = runner.play_to_end();
+        assert_eq!(121, result.scores[&result.winner]);
+    }
+
+    #[test]
+    fn partners_share_pegging_scores() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut third: SimplePlayer = SimplePlayer {};
+        let mut fourth: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::with_players(
+            vec![&mut first, &mut second, &mut third, &mut fourth],
+            GameRules::four_player_partners(),
+        );
+        components.current_player = PlayerPosition::Fourth;
+        components.hands.insert(
+            PlayerPosition::Fourth,
+            HashSet::from_iter(vec![Card::from("5s")]),
+        );
+        components.played = vec![Card::from("4c"), Card::from("6d")];
+        let rule = Rule { _state: Pegging };
+        let (_, result) = rule.apply(&mut components);
+        match result {
+            PlayResult::WaitingForPlay(result) => {
+                assert_eq!(0, *result.scores.get(&PlayerPosition::First).unwrap());
+                assert_eq!(5, *result.scores.get(&PlayerPosition::Second).unwrap());
+                assert_eq!(0, *result.scores.get(&PlayerPosition::Third).unwrap());
+                assert_eq!(5, *result.scores.get(&PlayerPosition::Fourth).unwrap());
+                assert_eq!(PlayerPosition::First, result.current_player);
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn partners_win_together() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut third: SimplePlayer = SimplePlayer {};
+        let mut fourth: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::with_players(
+            vec![&mut first, &mut second, &mut third, &mut fourth],
+            GameRules::four_player_partners(),
+        );
+        components.scores = HashMap::from([
+            (PlayerPosition::First, 120),
+            (PlayerPosition::Second, 100),
+            (PlayerPosition::Third, 120),
+            (PlayerPosition::Fourth, 100),
+        ]);
+        components.current_player = PlayerPosition::Third;
+        let rule = Rule { _state: SecondGo };
+        let (_, result) = rule.apply(&mut components);
+        match result {
+            PlayResult::GameOver(result) => {
+                assert_eq!(PlayerPosition::Third, result.winner);
+                assert_eq!(121, *result.scores.get(&PlayerPosition::First).unwrap());
+                assert_eq!(121, *result.scores.get(&PlayerPosition::Third).unwrap());
+                assert_eq!(None, result.skunk);
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
+    #[test]
+    fn plays_a_whole_partnership_game() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut third: SimplePlayer = SimplePlayer {};
+        let mut fourth: SimplePlayer = SimplePlayer {};
+        let rules = GameRules::four_player_partners();
+        let mut runner = GameRunner::with_players(
+            vec![&mut first, &mut second, &mut third, &mut fourth],
+            rules,
+        )
+        .unwrap();
+        let result = runner.play_to_end();
+        let partner = rules.partner(result.winner).unwrap();
+        assert_eq!(121, *result.scores.get(&result.winner).unwrap());
+        assert_eq!(121, *result.scores.get(&partner).unwrap());
+    }
+
+    #[test]
+    fn pone_gets_three_for_last() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::new(&mut first, &mut second);
+        components.rules = GameRules::five_card();
+        components.deck = Deck::stacked(vec![Card::from("Kd"), Card::from("2c")]);
+        let rule = Rule { _state: ChooseDealer };
+        rule.apply(&mut components);
+        assert_eq!(0, *components.scores.get(&PlayerPosition::Second).unwrap());
+        assert_eq!(3, *components.scores.get(&PlayerPosition::First).unwrap());
+    }
+
+    #[test]
+    fn deals_five_card_hands() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut components = GameComponents::new(&mut first, &mut second);
+        components.rules = GameRules::five_card();
+        let rule = Rule { _state: Deal };
+        rule.apply(&mut components);
+        assert!(components.crib.is_empty());
+        let rule = Rule { _state: ChooseCrib };
+        let (_, result) = rule.apply(&mut components);
+        match result {
+            PlayResult::CribChosen(result) => {
+                assert_eq!(3, result.hands.get(&PlayerPosition::First).unwrap().len());
+                assert_eq!(3, result.hands.get(&PlayerPosition::Second).unwrap().len());
+            }
+            _ => panic!("Wrong rule result"),
+        }
+        assert_eq!(4, components.crib.len());
+    }
+
+    #[test]
+    fn plays_a_whole_five_card_game() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut runner = GameRunner::new(&mut first, &mut second, GameRules::five_card()).unwrap();
+        let result = runner.play_to_end();
+        assert_eq!(61, *result.scores.get(&result.winner).unwrap());
+    }
+
+    #[test]
+    fn first_dealer_can_be_chosen() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut runner = GameRunner::new(&mut first, &mut second, GameRules::five_card()).unwrap();
+        runner.set_first_dealer(PlayerPosition::Second);
+        match runner.step() {
+            PlayResult::CardsDealt(result) => {
+                assert_eq!(PlayerPosition::Second, result.dealer);
+                assert_eq!(3, *result.scores.get(&PlayerPosition::First).unwrap());
+            }
+            _ => panic!("Wrong rule result"),
+        }
+    }
+
     /*
     #[test]
     fn can_win_on_a_pone_count() {
@@ -1,5 +1,11 @@
 pub mod combinatorics;
 pub mod deck;
 pub mod game;
+pub mod match_play;
 pub mod player;
+pub mod rules;
 pub mod scoring;
+pub mod simulation;
+pub mod stats;
+pub mod strategy;
+pub mod tournament;
@@ -0,0 +1,205 @@
+use crate::game::{GameResult, GameRunner, SeatingError};
+use crate::player::{KnowsCribbage, PlayerPosition};
+use crate::rules::{GameRules, Skunk};
+use std::collections::HashMap;
+
+/// How long a match goes on for.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum MatchLength {
+    /// The first player to win more than half of this many games takes the
+    /// match.
+    BestOf(u32),
+    /// The first player to this many match points takes the match.
+    FirstTo(u32),
+}
+
+/// The match points a game is worth to its winner. Skunking the loser is
+/// worth more.
+pub fn match_points(skunk: Option<Skunk>) -> u32 {
+    match skunk {
+        None => 1,
+        Some(Skunk::Single) => 2,
+        Some(Skunk::Double) => 3,
+    }
+}
+
+/// How a match went, game by game.
+pub struct MatchSummary {
+    pub games: Vec<GameResult>,
+    pub games_won: HashMap<PlayerPosition, u32>,
+    pub match_points: HashMap<PlayerPosition, u32>,
+    /// How many times each player skunked the other.
+    pub skunks: HashMap<PlayerPosition, u32>,
+    /// How many times each player double skunked the other.
+    pub double_skunks: HashMap<PlayerPosition, u32>,
+    pub winner: Option<PlayerPosition>,
+}
+
+impl MatchSummary {
+    fn new() -> Self {
+        let zeroes = || PlayerPosition::iter(2).map(|p| (p, 0)).collect();
+        Self {
+            games: Vec::new(),
+            games_won: zeroes(),
+            match_points: zeroes(),
+            skunks: zeroes(),
+            double_skunks: zeroes(),
+            winner: None,
+        }
+    }
+
+    fn record(&mut self, result: GameResult, length: MatchLength) {
+        let winner = result.winner;
+        *self.games_won.get_mut(&winner).unwrap() += 1;
+        *self.match_points.get_mut(&winner).unwrap() += match_points(result.skunk);
+        match result.skunk {
+            Some(Skunk::Single) => *self.skunks.get_mut(&winner).unwrap() += 1,
+            Some(Skunk::Double) => *self.double_skunks.get_mut(&winner).unwrap() += 1,
+            None => (),
+        }
+        self.games.push(result);
+
+        let (tally, needed) = match length {
+            MatchLength::BestOf(games) => (&self.games_won, games / 2 + 1),
+            MatchLength::FirstTo(points) => (&self.match_points, points),
+        };
+        if tally[&winner] >= needed {
+            self.winner = Some(winner);
+        }
+    }
+}
+
+/// A series of two-player games between the same players.
+pub struct Match<'p> {
+    first: &'p mut dyn KnowsCribbage,
+    second: &'p mut dyn KnowsCribbage,
+    rules: GameRules,
+    length: MatchLength,
+}
+
+impl<'p> Match<'p> {
+    /// Sets up a match, as long as the rules are for the two players.
+    pub fn new(
+        first: &'p mut dyn KnowsCribbage,
+        second: &'p mut dyn KnowsCribbage,
+        rules: GameRules,
+        length: MatchLength,
+    ) -> Result<Self, SeatingError> {
+        if rules.players != 2 {
+            return Err(SeatingError::WrongNumberOfPlayers {
+                rules: rules.players,
+                seated: 2,
+            });
+        }
+        Ok(Self {
+            first,
+            second,
+            rules,
+            length,
+        })
+    }
+
+    /// Plays games until somebody has won the match. The players cut for
+    /// the first deal, and after that the loser of each game deals first in
+    /// the next.
+    pub fn play(&mut self) -> MatchSummary {
+        let mut summary = MatchSummary::new();
+        let mut first_dealer = None;
+        while summary.winner.is_none() {
+            let mut runner = GameRunner::new(&mut *self.first, &mut *self.second, self.rules)
+                .expect("Match::new only takes rules for two players");
+            if let Some(dealer) = first_dealer {
+                runner.set_first_dealer(dealer);
+            }
+            let result = runner.play_to_end();
+            first_dealer = Some(result.winner.next(self.rules.players));
+            summary.record(result, self.length);
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::Card;
+    use crate::player::SimplePlayer;
+
+    fn result(winner: PlayerPosition, loser_score: u8) -> GameResult {
+        let rules = GameRules::new();
+        GameResult {
+            winner,
+            scores: HashMap::from([(winner, 121), (winner.next(2), loser_score)]),
+            up_card: Card::from("As"),
+            hands: HashMap::new(),
+            skunk: rules.skunk(loser_score),
+        }
+    }
+
+    #[test]
+    fn skunks_are_worth_more() {
+        assert_eq!(1, match_points(None));
+        assert_eq!(2, match_points(Some(Skunk::Single)));
+        assert_eq!(3, match_points(Some(Skunk::Double)));
+    }
+
+    #[test]
+    fn best_of_counts_games() {
+        let mut summary = MatchSummary::new();
+        summary.record(result(PlayerPosition::First, 40), MatchLength::BestOf(3));
+        assert_eq!(None, summary.winner);
+        summary.record(result(PlayerPosition::Second, 100), MatchLength::BestOf(3));
+        assert_eq!(None, summary.winner);
+        summary.record(result(PlayerPosition::First, 100), MatchLength::BestOf(3));
+        assert_eq!(Some(PlayerPosition::First), summary.winner);
+        assert_eq!(4, summary.match_points[&PlayerPosition::First]);
+        assert_eq!(1, summary.double_skunks[&PlayerPosition::First]);
+    }
+
+    #[test]
+    fn first_to_counts_match_points() {
+        let mut summary = MatchSummary::new();
+        summary.record(result(PlayerPosition::Second, 80), MatchLength::FirstTo(3));
+        assert_eq!(None, summary.winner);
+        assert_eq!(1, summary.skunks[&PlayerPosition::Second]);
+        summary.record(result(PlayerPosition::Second, 110), MatchLength::FirstTo(3));
+        assert_eq!(Some(PlayerPosition::Second), summary.winner);
+        assert_eq!(2, summary.games_won[&PlayerPosition::Second]);
+    }
+
+    #[test]
+    fn plays_a_whole_match() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let mut series = Match::new(
+            &mut first,
+            &mut second,
+            GameRules::new(),
+            MatchLength::BestOf(5),
+        )
+        .unwrap();
+        let summary = series.play();
+        let winner = summary.winner.unwrap();
+        assert_eq!(3, summary.games_won[&winner]);
+        assert!(summary.games.len() >= 3 && summary.games.len() <= 5);
+    }
+
+    #[test]
+    fn matches_are_for_two_players() {
+        let mut first: SimplePlayer = SimplePlayer {};
+        let mut second: SimplePlayer = SimplePlayer {};
+        let series = Match::new(
+            &mut first,
+            &mut second,
+            GameRules::three_player(),
+            MatchLength::BestOf(5),
+        );
+        assert_eq!(
+            Some(SeatingError::WrongNumberOfPlayers {
+                rules: 3,
+                seated: 2
+            }),
+            series.err()
+        );
+    }
+}
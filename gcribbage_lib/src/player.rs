@@ -5,35 +5,89 @@ use std::collections::HashSet;
 pub enum PlayerPosition {
     First,
     Second,
+    Third,
+    Fourth,
 }
 
+/// Every seat there can be at the table, in the order play goes around it.
+const SEATS: [PlayerPosition; 4] = [
+    PlayerPosition::First,
+    PlayerPosition::Second,
+    PlayerPosition::Third,
+    PlayerPosition::Fourth,
+];
+
 impl PlayerPosition {
-    pub fn next(self) -> Self {
-        use PlayerPosition::*;
-        match self {
-            First => Second,
-            Second => First,
-        }
+    /// Makes an iterator for the seats at a table with the given number of
+    /// players, in the order play goes around the table.
+    pub fn iter(players: usize) -> impl Iterator<Item = Self> {
+        SEATS.into_iter().take(players)
+    }
+
+    /// The seat of the player that plays after this one, at a table with
+    /// the given number of players.
+    pub fn next(self, players: usize) -> Self {
+        let seat = self as usize;
+        assert!(seat < players, "Seat is not at this table");
+        SEATS[(seat + 1) % players]
     }
 }
 
 pub trait KnowsCribbage {
-    fn choose_crib(&mut self, hand: &HashSet<Card>) -> Vec<Card>;
-    fn play(&mut self, hand: &HashSet<Card>) -> Card;
+    /// Picks the given number of cards from the hand to give to the crib.
+    /// The crib is the player's own if they or their partner are dealing.
+    fn choose_crib(&mut self, hand: &HashSet<Card>, discards: usize, own_crib: bool) -> Vec<Card>;
+    /// Picks a card from the hand to play onto the cards played since the
+    /// count last started. At least one of them can be played without
+    /// going over 31.
+    fn play(&mut self, hand: &HashSet<Card>, played: &[Card]) -> Card;
+}
+
+/// A player lent to a game plays just as it would if the game owned it.
+impl<P: KnowsCribbage + ?Sized> KnowsCribbage for &mut P {
+    fn choose_crib(&mut self, hand: &HashSet<Card>, discards: usize, own_crib: bool) -> Vec<Card> {
+        (**self).choose_crib(hand, discards, own_crib)
+    }
+
+    fn play(&mut self, hand: &HashSet<Card>, played: &[Card]) -> Card {
+        (**self).play(hand, played)
+    }
 }
 
 pub struct SimplePlayer {}
 
 impl KnowsCribbage for SimplePlayer {
-    fn choose_crib(&mut self, hand: &HashSet<Card>) -> Vec<Card> {
+    fn choose_crib(&mut self, hand: &HashSet<Card>, discards: usize, _own_crib: bool) -> Vec<Card> {
         let mut sorted = hand.iter().copied().collect::<Vec<Card>>();
         sorted.sort();
-        sorted[0..2].to_vec()
+        sorted[0..discards].to_vec()
     }
 
-    fn play(&mut self, hand: &HashSet<Card>) -> Card {
+    fn play(&mut self, hand: &HashSet<Card>, _played: &[Card]) -> Card {
         let mut sorted = hand.iter().copied().collect::<Vec<Card>>();
         sorted.sort();
         sorted[0]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_goes_around_the_table() {
+        use PlayerPosition::*;
+        let tests = vec![
+            (2, First, Second),
+            (2, Second, First),
+            (3, Second, Third),
+            (3, Third, First),
+            (4, Third, Fourth),
+            (4, Fourth, First),
+        ];
+
+        for (players, position, expected) in tests {
+            assert_eq!(expected, position.next(players), "{:?}", position);
+        }
+    }
+}
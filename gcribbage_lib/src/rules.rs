@@ -0,0 +1,163 @@
+use crate::player::PlayerPosition;
+
+/// How badly the loser of a game was beaten.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Skunk {
+    Single,
+    Double,
+}
+
+/// The rules a game is played by. The defaults are the standard rules for
+/// a two-player game to 121.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct GameRules {
+    /// How many players are at the table.
+    pub players: usize,
+    /// How many cards each player is dealt.
+    pub deal_size: usize,
+    /// How many cards each player gives to the crib. If that leaves the crib
+    /// short, it is made up from the deck.
+    pub discards: usize,
+    /// Whether players sitting across from each other play as partners,
+    /// sharing a score.
+    pub partnerships: bool,
+    /// Points the player to the left of the first dealer starts the game
+    /// with, to make up for the dealer having the first crib.
+    pub pone_head_start: u8,
+    /// The score a player needs to reach to win.
+    pub target_score: u8,
+    /// Whether a four-card flush in the crib scores, or only five-card ones.
+    pub crib_four_card_flush: bool,
+    /// What the dealer pegs when the up card is a jack.
+    pub nibs_value: u8,
+    /// Whether tied cards when cutting for deal are thrown back for a new
+    /// cut. If not, the suit breaks the tie.
+    pub redeal_on_tied_cut: bool,
+    /// A loser with fewer points than this has been skunked.
+    pub skunk_line: u8,
+    /// A loser with fewer points than this has been double skunked.
+    pub double_skunk_line: u8,
+}
+
+impl GameRules {
+    pub fn new() -> Self {
+        Self {
+            players: 2,
+            deal_size: 6,
+            discards: 2,
+            partnerships: false,
+            pone_head_start: 0,
+            target_score: 121,
+            crib_four_card_flush: false,
+            nibs_value: 2,
+            redeal_on_tied_cut: true,
+            skunk_line: 91,
+            double_skunk_line: 61,
+        }
+    }
+
+    /// The rules for a short game to 61, with the skunk lines moved down
+    /// to match.
+    pub fn short_game() -> Self {
+        Self {
+            target_score: 61,
+            skunk_line: 31,
+            double_skunk_line: 0,
+            ..Self::new()
+        }
+    }
+
+    /// The rules for three players, who are each dealt five cards and give
+    /// one to the crib. The crib gets its fourth card from the deck.
+    pub fn three_player() -> Self {
+        Self {
+            players: 3,
+            deal_size: 5,
+            discards: 1,
+            ..Self::new()
+        }
+    }
+
+    /// The rules for four players in two partnerships. Everyone is dealt
+    /// five cards and gives one to the crib.
+    pub fn four_player_partners() -> Self {
+        Self {
+            players: 4,
+            deal_size: 5,
+            discards: 1,
+            partnerships: true,
+            ..Self::new()
+        }
+    }
+
+    /// The rules for five-card cribbage. Each player is dealt five cards
+    /// and gives two to the crib, and the pone gets "three for last" to
+    /// start a game to 61.
+    pub fn five_card() -> Self {
+        Self {
+            deal_size: 5,
+            pone_head_start: 3,
+            ..Self::short_game()
+        }
+    }
+
+    /// The partner of the player in the given seat, if there are
+    /// partnerships.
+    pub fn partner(&self, position: PlayerPosition) -> Option<PlayerPosition> {
+        match self.partnerships {
+            true => Some(position.next(self.players).next(self.players)),
+            false => None,
+        }
+    }
+
+    /// Works out whether a loser with the given score has been skunked.
+    pub fn skunk(&self, loser_score: u8) -> Option<Skunk> {
+        if loser_score < self.double_skunk_line {
+            Some(Skunk::Double)
+        } else if loser_score < self.skunk_line {
+            Some(Skunk::Single)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skunks() {
+        let tests = vec![
+            (GameRules::new(), 120, None),
+            (GameRules::new(), 91, None),
+            (GameRules::new(), 90, Some(Skunk::Single)),
+            (GameRules::new(), 61, Some(Skunk::Single)),
+            (GameRules::new(), 60, Some(Skunk::Double)),
+            (GameRules::short_game(), 31, None),
+            (GameRules::short_game(), 30, Some(Skunk::Single)),
+            (GameRules::short_game(), 0, Some(Skunk::Single)),
+        ];
+
+        for (rules, score, expected) in tests {
+            assert_eq!(expected, rules.skunk(score), "{}", score);
+        }
+    }
+
+    #[test]
+    fn partners_sit_across_the_table() {
+        use PlayerPosition::*;
+        let rules = GameRules::four_player_partners();
+        assert_eq!(Some(Third), rules.partner(First));
+        assert_eq!(Some(Fourth), rules.partner(Second));
+        assert_eq!(Some(First), rules.partner(Third));
+        assert_eq!(Some(Second), rules.partner(Fourth));
+        assert_eq!(None, GameRules::new().partner(First));
+    }
+}
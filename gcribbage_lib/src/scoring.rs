@@ -39,6 +39,7 @@ pub enum HandScorings {
     RunOfThree(HashSet<Card>),
     RunOfFour(HashSet<Card>),
     RunOfFive(HashSet<Card>),
+    ThreeCardFlush(HashSet<Card>),
     FourCardFlush(HashSet<Card>),
     FiveCardFlush(HashSet<Card>),
     Nobs(Card),
@@ -53,11 +54,59 @@ impl HandScorings {
             RunOfThree(_) => 3,
             RunOfFour(_) => 4,
             RunOfFive(_) => 5,
+            ThreeCardFlush(_) => 3,
             FourCardFlush(_) => 4,
             FiveCardFlush(_) => 5,
             Nobs(_) => 1,
         }
     }
+
+    /// What the score is called, such as "Fifteen" or "Run of 3".
+    pub fn name(&self) -> &'static str {
+        use HandScorings::*;
+        match self {
+            Fifteen(_) => "Fifteen",
+            Pair(_) => "Pair",
+            RunOfThree(_) => "Run of 3",
+            RunOfFour(_) => "Run of 4",
+            RunOfFive(_) => "Run of 5",
+            ThreeCardFlush(_) | FourCardFlush(_) | FiveCardFlush(_) => "Flush",
+            Nobs(_) => "His nob",
+        }
+    }
+
+    /// The cards that make the score, in order.
+    pub fn cards(&self) -> Vec<Card> {
+        use HandScorings::*;
+        let mut cards = match self {
+            Fifteen(cards)
+            | Pair(cards)
+            | RunOfThree(cards)
+            | RunOfFour(cards)
+            | RunOfFive(cards)
+            | ThreeCardFlush(cards)
+            | FourCardFlush(cards)
+            | FiveCardFlush(cards) => cards.iter().copied().collect::<Vec<Card>>(),
+            Nobs(jack) => vec![*jack],
+        };
+        cards.sort();
+        cards
+    }
+
+    /// The order scores are counted in: fifteens, pairs, runs, the flush
+    /// and then his nob, with scores of the same kind in the order of their
+    /// cards. Sort by this, as `score_hand` finds them in any order.
+    pub fn counting_order(&self) -> (u8, Vec<Card>) {
+        use HandScorings::*;
+        let kind = match self {
+            Fifteen(_) => 0,
+            Pair(_) => 1,
+            RunOfThree(_) | RunOfFour(_) | RunOfFive(_) => 2,
+            ThreeCardFlush(_) | FourCardFlush(_) | FiveCardFlush(_) => 3,
+            Nobs(_) => 4,
+        };
+        (kind, self.cards())
+    }
 }
 
 pub fn score_pegging(played: Vec<Card>) -> Vec<PeggingScorings> {
@@ -127,10 +176,8 @@ pub fn score_hand(hand: &HashSet<Card>, up_card: Card) -> Vec<HandScorings> {
     let mut scorings = Vec::new();
 
     let mut skip_runs = false;
-    let mut skip_flush = false;
     for pick in (2..=5).rev() {
         let mut run_found = false;
-        let mut flush_found = false;
         for combo in combinations(set.iter(), pick) {
             /* Find fifteen */
             if combo.iter().map(|c| c.rank.value()).sum::<u8>() == 15 {
@@ -170,40 +217,33 @@ pub fn score_hand(hand: &HashSet<Card>, up_card: Card) -> Vec<HandScorings> {
                     run_found = true;
                 }
             }
-
-            /* Find flush */
-            if pick > 3 && !skip_flush {
-                for suit in [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds] {
-                    let matching_cards = combo
-                        .iter()
-                        .filter(|c| c.suit == suit)
-                        .cloned()
-                        .collect::<HashSet<Card>>();
-                    match matching_cards.len() {
-                        4 => {
-                            scorings.push(HandScorings::FourCardFlush(matching_cards));
-                            flush_found = true;
-                        }
-                        5 => {
-                            scorings.push(HandScorings::FiveCardFlush(matching_cards));
-                            flush_found = true;
-                        }
-                        _ => (),
-                    }
-                }
-            }
         }
         if run_found {
             skip_runs = true;
         }
+    }
 
-        if flush_found {
-            skip_flush = true;
+    /* Find flush. The whole hand has to be one suit, and the up card only
+     * adds to it if it matches. */
+    for suit in [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds] {
+        if hand.is_empty() || !hand.iter().all(|c| c.suit == suit) {
+            continue;
+        }
+        let matching_cards = set
+            .iter()
+            .filter(|c| c.suit == suit)
+            .cloned()
+            .collect::<HashSet<Card>>();
+        match matching_cards.len() {
+            3 => scorings.push(HandScorings::ThreeCardFlush(matching_cards)),
+            4 => scorings.push(HandScorings::FourCardFlush(matching_cards)),
+            5 => scorings.push(HandScorings::FiveCardFlush(matching_cards)),
+            _ => (),
         }
     }
 
-    /* Find nobs */
-    for jack in set.iter().filter(|c| c.rank == Rank::Jack) {
+    /* Find nobs. Only a jack in the hand counts, not one turned up. */
+    for jack in hand.iter().filter(|c| c.rank == Rank::Jack) {
         if jack.suit == up_card.suit {
             scorings.push(HandScorings::Nobs(*jack));
             break;
@@ -221,10 +261,13 @@ pub fn score_crib(hand: &HashSet<Card>, up_card: Card) -> Vec<HandScorings> {
 }
 
 #[cfg(test)]
+#[allow(clippy::ptr_arg, clippy::type_complexity, clippy::unnecessary_to_owned)]
 mod hand_scoring {
     use super::*;
     use crate::deck::Card;
 
+    type ExpectedAndTotal = (&'static str, HashSet<Card>, Card, Vec<HandScorings>, u8);
+
     fn contains(hand: &HashSet<Card>, up_card: &Card, expected: &Vec<HandScorings>) -> bool {
         let scorings = score_hand(hand, *up_card);
         for scoring in expected.iter().cloned() {
@@ -882,6 +925,73 @@ mod hand_scoring {
         }
     }
 
+    #[test]
+    fn flush_must_be_whole_hand() {
+        let hand = HashSet::from_iter(vec![
+            Card::from("As"),
+            Card::from("3s"),
+            Card::from("7s"),
+            Card::from("9h"),
+        ]);
+        let result = score_hand(&hand, Card::from("Js"));
+        assert!(!result
+            .iter()
+            .any(|s| matches!(s, HandScorings::FourCardFlush(_))));
+    }
+
+    #[test]
+    fn three_card_hands() {
+        let tests: Vec<ExpectedAndTotal> = Vec::from([
+            (
+                "Three card flush",
+                HashSet::from_iter(vec![Card::from("2h"), Card::from("6h"), Card::from("Th")]),
+                Card::from("Ks"),
+                Vec::from([HandScorings::ThreeCardFlush(HashSet::from_iter([
+                    Card::from("2h"),
+                    Card::from("6h"),
+                    Card::from("Th"),
+                ]))]),
+                3,
+            ),
+            (
+                "Three card flush with up card",
+                HashSet::from_iter(vec![Card::from("2h"), Card::from("6h"), Card::from("Th")]),
+                Card::from("Kh"),
+                Vec::from([HandScorings::FourCardFlush(HashSet::from_iter([
+                    Card::from("2h"),
+                    Card::from("6h"),
+                    Card::from("Th"),
+                    Card::from("Kh"),
+                ]))]),
+                4,
+            ),
+            (
+                "Fifteens and a run",
+                HashSet::from_iter(vec![Card::from("4c"), Card::from("5d"), Card::from("6h")]),
+                Card::from("Ks"),
+                Vec::from([
+                    HandScorings::Fifteen(HashSet::from_iter([
+                        Card::from("4c"),
+                        Card::from("5d"),
+                        Card::from("6h"),
+                    ])),
+                    HandScorings::Fifteen(HashSet::from_iter([Card::from("5d"), Card::from("Ks")])),
+                    HandScorings::RunOfThree(HashSet::from_iter([
+                        Card::from("4c"),
+                        Card::from("5d"),
+                        Card::from("6h"),
+                    ])),
+                ]),
+                7,
+            ),
+        ]);
+        for (description, hand, up_card, expected, total) in tests {
+            assert!(contains(&hand, &up_card, &expected,), "{}", description,);
+            let scored: u8 = score_hand(&hand, up_card).iter().map(|s| s.value()).sum();
+            assert_eq!(total, scored, "{}", description);
+        }
+    }
+
     #[test]
     fn nobs() {
         let tests: Vec<(&str, HashSet<Card>, Card, Vec<HandScorings>)> = Vec::from([
@@ -934,9 +1044,53 @@ mod hand_scoring {
             assert!(contains(&hand, &up_card, &expected,), "{}", description,);
         }
     }
+
+    #[test]
+    fn scores_sort_into_counting_order() {
+        let hand = HashSet::from_iter(vec![
+            Card::from("Jh"),
+            Card::from("5d"),
+            Card::from("4h"),
+            Card::from("6h"),
+        ]);
+        let mut scorings = score_hand(&hand, Card::from("5h"));
+        scorings.sort_by_key(HandScorings::counting_order);
+        let listed = scorings
+            .iter()
+            .map(|s| (s.name(), s.cards()))
+            .collect::<Vec<_>>();
+
+        let cards = |names: &[&str]| names.iter().copied().map(Card::from).collect::<Vec<_>>();
+        let expected = vec![
+            ("Fifteen", cards(&["4H", "5H", "6H"])),
+            ("Fifteen", cards(&["4H", "5D", "6H"])),
+            ("Fifteen", cards(&["5H", "JH"])),
+            ("Fifteen", cards(&["5D", "JH"])),
+            ("Pair", cards(&["5H", "5D"])),
+            ("Run of 3", cards(&["4H", "5H", "6H"])),
+            ("Run of 3", cards(&["4H", "5D", "6H"])),
+            ("His nob", cards(&["JH"])),
+        ];
+        assert_eq!(expected, listed);
+    }
+
+    #[test]
+    fn no_nobs_for_up_card_jack() {
+        let hand = HashSet::from_iter(vec![
+            Card::from("5h"),
+            Card::from("5d"),
+            Card::from("5s"),
+            Card::from("5c"),
+        ]);
+        let scorings = score_hand(&hand, Card::from("Jc"));
+        assert!(!scorings.iter().any(|s| matches!(s, HandScorings::Nobs(_))));
+        let scored: u8 = scorings.iter().map(|s| s.value()).sum();
+        assert_eq!(28, scored);
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::ptr_arg, clippy::type_complexity, clippy::unnecessary_to_owned)]
 mod crib_scoring {
     use super::*;
     use crate::deck::Card;
@@ -1023,6 +1177,7 @@ mod crib_scoring {
 }
 
 #[cfg(test)]
+#[allow(clippy::unnecessary_to_owned)]
 mod pegging_scoring {
     use super::*;
     use crate::deck::Card;
@@ -0,0 +1,173 @@
+use crate::game::{GameResult, GameRunner, PlayResult, SeatingError};
+use crate::player::{KnowsCribbage, PlayerPosition};
+use crate::rules::GameRules;
+use crate::stats::Statistics;
+
+/// How many standard deviations either side of a win rate its 95%
+/// confidence interval reaches.
+const Z_95: f64 = 1.96;
+
+/// The seed for a given game in a batch, so every game is dealt
+/// differently but the batch can be played again.
+pub fn game_seed(seed: u64, game: u32) -> u64 {
+    seed.wrapping_add(game as u64)
+}
+
+/// The 95% confidence interval for the chance of winning, from how many of
+/// some games were won. This is the Wilson score interval, which stays
+/// sensible near 0% and 100% and for small numbers of games.
+pub fn win_interval(wins: u32, games: u32) -> (f64, f64) {
+    if games == 0 {
+        return (0.0, 1.0);
+    }
+    let n = games as f64;
+    let p = wins as f64 / n;
+    let z2 = Z_95 * Z_95;
+    let centre = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let spread = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+    ((centre - spread).max(0.0), (centre + spread).min(1.0))
+}
+
+/// How a batch of two-player games between the same computer players went.
+pub struct Simulation {
+    pub games: Vec<GameResult>,
+    /// How each seat scored, pegging and counting, over all the games.
+    pub statistics: Statistics,
+}
+
+impl Simulation {
+    /// Plays the given number of games between two players, who keep their
+    /// seats throughout. Each game is shuffled from the seed, and the deal
+    /// alternates between the players so neither has the advantage of
+    /// always having the first crib. The rules have to be for two players.
+    pub fn run(
+        first: &mut dyn KnowsCribbage,
+        second: &mut dyn KnowsCribbage,
+        rules: GameRules,
+        games: u32,
+        seed: u64,
+    ) -> Result<Self, SeatingError> {
+        if rules.players != 2 {
+            return Err(SeatingError::WrongNumberOfPlayers {
+                rules: rules.players,
+                seated: 2,
+            });
+        }
+        let mut simulation = Self {
+            games: Vec::new(),
+            statistics: Statistics::new(rules),
+        };
+        for game in 0..games {
+            let mut runner = GameRunner::new(&mut *first, &mut *second, rules)?;
+            runner.set_seed(game_seed(seed, game));
+            runner.set_first_dealer(match game % 2 {
+                0 => PlayerPosition::First,
+                _ => PlayerPosition::Second,
+            });
+            simulation.statistics.new_game(rules);
+            loop {
+                let result = runner.step();
+                simulation.statistics.record(&result);
+                if let PlayResult::GameOver(result) = result {
+                    simulation.games.push(result);
+                    break;
+                }
+            }
+        }
+        Ok(simulation)
+    }
+
+    pub fn wins(&self, position: PlayerPosition) -> u32 {
+        self.games.iter().filter(|g| g.winner == position).count() as u32
+    }
+
+    pub fn win_rate(&self, position: PlayerPosition) -> f64 {
+        match self.games.len() {
+            0 => 0.0,
+            games => self.wins(position) as f64 / games as f64,
+        }
+    }
+
+    pub fn win_interval(&self, position: PlayerPosition) -> (f64, f64) {
+        win_interval(self.wins(position), self.games.len() as u32)
+    }
+
+    /// The average points the player finished ahead of the other, which is
+    /// negative if they usually finished behind.
+    pub fn average_margin(&self, position: PlayerPosition) -> f64 {
+        let margins = self.games.iter().map(|g| {
+            let score = |p| g.scores.get(&p).copied().unwrap_or(0) as f64;
+            score(position) - score(position.next(2))
+        });
+        match self.games.len() {
+            0 => 0.0,
+            games => margins.sum::<f64>() / games as f64,
+        }
+    }
+
+    /// How often the player skunked the other, out of all the games.
+    pub fn skunk_rate(&self, position: PlayerPosition) -> f64 {
+        let skunks = self
+            .games
+            .iter()
+            .filter(|g| g.winner == position && g.skunk.is_some())
+            .count();
+        match self.games.len() {
+            0 => 0.0,
+            games => skunks as f64 / games as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::SimplePlayer;
+
+    #[test]
+    fn win_intervals_narrow_with_more_games() {
+        let (low, high) = win_interval(5, 10);
+        assert!(low < 0.5 && high > 0.5);
+        let (more_low, more_high) = win_interval(500, 1000);
+        assert!(more_low > low && more_high < high);
+        assert_eq!((0.0, 1.0), win_interval(0, 0));
+        assert_eq!(0.0, win_interval(0, 10).0);
+        assert_eq!(1.0, win_interval(10, 10).1);
+    }
+
+    #[test]
+    fn seeded_batches_play_the_same() {
+        let run = || {
+            let mut first = SimplePlayer {};
+            let mut second = SimplePlayer {};
+            Simulation::run(&mut first, &mut second, GameRules::new(), 4, 11).unwrap()
+        };
+        let (once, again) = (run(), run());
+
+        assert_eq!(4, once.games.len());
+        for (a, b) in once.games.iter().zip(&again.games) {
+            assert_eq!(a.winner, b.winner);
+            assert_eq!(a.scores, b.scores);
+        }
+        let wins = once.wins(PlayerPosition::First) + once.wins(PlayerPosition::Second);
+        assert_eq!(4, wins);
+        assert_eq!(
+            once.average_margin(PlayerPosition::First),
+            -once.average_margin(PlayerPosition::Second)
+        );
+    }
+
+    #[test]
+    fn simulations_are_for_two_players() {
+        let mut first = SimplePlayer {};
+        let mut second = SimplePlayer {};
+        let simulation = Simulation::run(&mut first, &mut second, GameRules::three_player(), 0, 11);
+        assert_eq!(
+            Some(SeatingError::WrongNumberOfPlayers {
+                rules: 3,
+                seated: 2
+            }),
+            simulation.err()
+        );
+    }
+}
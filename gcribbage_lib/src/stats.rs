@@ -0,0 +1,327 @@
+use crate::deck::Card;
+use crate::game::{GameResult, PlayResult};
+use crate::player::PlayerPosition;
+use crate::rules::GameRules;
+use crate::scoring::HandScorings;
+use std::collections::{HashMap, HashSet};
+
+/// The most a hand can score.
+pub const MAX_HAND_SCORE: usize = 29;
+
+/// How many of a player's best hands are kept.
+pub const BEST_HANDS: usize = 5;
+
+/// A hand worth remembering, with the up card it was counted with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestHand {
+    pub hand: Vec<Card>,
+    pub up_card: Card,
+    pub score: u8,
+}
+
+/// How a player has done over every game they've played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerStats {
+    pub games_won: u32,
+    pub games_lost: u32,
+    /// Games won with the loser short of the skunk line, counting double
+    /// skunks.
+    pub skunks_won: u32,
+    pub skunks_lost: u32,
+    /// How many hands the player has been dealt.
+    pub deals: u32,
+    pub pegging_points: u32,
+    /// How many hands have been counted, not counting one the player
+    /// pegged out with part way through.
+    pub hands: u32,
+    pub hand_points: u32,
+    pub cribs: u32,
+    pub crib_points: u32,
+    /// How many hands have been counted at each score, from 0 to 29.
+    pub hand_scores: [u32; MAX_HAND_SCORE + 1],
+    /// The best hands ever counted, best first.
+    pub best_hands: Vec<BestHand>,
+}
+
+impl Default for PlayerStats {
+    fn default() -> Self {
+        Self {
+            games_won: 0,
+            games_lost: 0,
+            skunks_won: 0,
+            skunks_lost: 0,
+            deals: 0,
+            pegging_points: 0,
+            hands: 0,
+            hand_points: 0,
+            cribs: 0,
+            crib_points: 0,
+            hand_scores: [0; MAX_HAND_SCORE + 1],
+            best_hands: Vec::new(),
+        }
+    }
+}
+
+fn average(points: u32, count: u32) -> f64 {
+    match count {
+        0 => 0.0,
+        _ => points as f64 / count as f64,
+    }
+}
+
+impl PlayerStats {
+    pub fn games(&self) -> u32 {
+        self.games_won + self.games_lost
+    }
+
+    pub fn average_hand(&self) -> f64 {
+        average(self.hand_points, self.hands)
+    }
+
+    pub fn average_crib(&self) -> f64 {
+        average(self.crib_points, self.cribs)
+    }
+
+    /// The average pegged in a deal, which includes anything for his heels.
+    pub fn average_pegging(&self) -> f64 {
+        average(self.pegging_points, self.deals)
+    }
+
+    fn count_hand(&mut self, hand: &HashSet<Card>, up_card: Card, score: u8) {
+        self.hands += 1;
+        self.hand_points += score as u32;
+        self.hand_scores[(score as usize).min(MAX_HAND_SCORE)] += 1;
+        let mut hand = hand.iter().copied().collect::<Vec<Card>>();
+        hand.sort();
+        let best = BestHand {
+            hand,
+            up_card,
+            score,
+        };
+        /* Earlier hands stay ahead of later ones worth the same. */
+        let place = self.best_hands.partition_point(|b| b.score >= score);
+        if place < BEST_HANDS {
+            self.best_hands.insert(place, best);
+            self.best_hands.truncate(BEST_HANDS);
+        }
+    }
+}
+
+/// Keeps a tally for each player of how they score, from what the game
+/// reports as it's played. Every result from a game has to be recorded, in
+/// order, for the tally to come out right.
+///
+/// In a partnership game, partners share a score, so both are credited with
+/// what either of them pegs.
+pub struct Statistics {
+    pub players: HashMap<PlayerPosition, PlayerStats>,
+    rules: GameRules,
+    /// The scores as last reported.
+    scores: HashMap<PlayerPosition, u8>,
+    crib: HashSet<Card>,
+    /// Whether pegging is over and the hands are being counted.
+    counting: bool,
+}
+
+impl Statistics {
+    pub fn new(rules: GameRules) -> Self {
+        Self::with_players(HashMap::new(), rules)
+    }
+
+    /// Carries on tallying from the given stats, such as ones saved from
+    /// earlier games.
+    pub fn with_players(players: HashMap<PlayerPosition, PlayerStats>, rules: GameRules) -> Self {
+        Self {
+            players,
+            rules,
+            scores: HashMap::new(),
+            crib: HashSet::new(),
+            counting: false,
+        }
+    }
+
+    /// Starts tallying a new game, played by the given rules. A game that
+    /// was being played is left out of the tally from here on.
+    pub fn new_game(&mut self, rules: GameRules) {
+        let players = std::mem::take(&mut self.players);
+        *self = Self::with_players(players, rules);
+    }
+
+    pub fn player(&self, position: PlayerPosition) -> PlayerStats {
+        self.players.get(&position).cloned().unwrap_or_default()
+    }
+
+    fn stats(&mut self, position: PlayerPosition) -> &mut PlayerStats {
+        self.players.entry(position).or_default()
+    }
+
+    /// Credits each player with whatever their score has gone up by since
+    /// it was last reported, as pegging.
+    fn pegged(&mut self, scores: &HashMap<PlayerPosition, u8>) {
+        for (position, score) in scores {
+            let before = self.scores.get(position).copied().unwrap_or(0);
+            if *score > before {
+                self.stats(*position).pegging_points += (score - before) as u32;
+            }
+        }
+        self.scores = scores.clone();
+    }
+
+    pub fn record(&mut self, result: &PlayResult) {
+        match result {
+            PlayResult::DealerChosen(_) | PlayResult::Incomplete => (),
+            PlayResult::CardsDealt(info) => {
+                /* Anything the pone was given to start with isn't pegged. */
+                self.scores = info.scores.clone();
+                self.crib.clear();
+                self.counting = false;
+                for position in info.hands.keys() {
+                    self.stats(*position).deals += 1;
+                }
+            }
+            PlayResult::CribChosen(info) => {
+                self.crib = info.crib.clone();
+                self.scores = info.scores.clone();
+            }
+            PlayResult::WaitingForPlay(info) => self.pegged(&info.scores),
+            PlayResult::Go(info) => self.pegged(&info.scores),
+            PlayResult::WaitingForLastCardAcknowledgement(info) => {
+                self.pegged(&info.scores);
+                self.counting = info.hands.values().all(HashSet::is_empty);
+            }
+            PlayResult::HandScore(info) => {
+                let score = info
+                    .scorings
+                    .iter()
+                    .flatten()
+                    .map(HandScorings::value)
+                    .sum();
+                let stats = self.players.entry(info.player).or_default();
+                match info.hand == self.crib {
+                    true => {
+                        stats.cribs += 1;
+                        stats.crib_points += score as u32;
+                    }
+                    false => stats.count_hand(&info.hand, info.up_card, score),
+                }
+                self.scores = info.scores.clone();
+            }
+            PlayResult::GameOver(result) => self.game_over(result),
+        }
+    }
+
+    fn game_over(&mut self, result: &GameResult) {
+        /* The winning hand's score isn't reported, just that it pegged out,
+         * so only points pegged in play can be credited. */
+        if !self.counting {
+            self.pegged(&result.scores);
+        }
+        let winners = [Some(result.winner), self.rules.partner(result.winner)];
+        for position in result.scores.keys() {
+            let skunk = result.skunk.is_some() as u32;
+            let stats = self.players.entry(*position).or_default();
+            match winners.contains(&Some(*position)) {
+                true => {
+                    stats.games_won += 1;
+                    stats.skunks_won += skunk;
+                }
+                false => {
+                    stats.games_lost += 1;
+                    stats.skunks_lost += skunk;
+                }
+            }
+        }
+        self.new_game(self.rules);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameRunner;
+    use crate::player::SimplePlayer;
+
+    fn cards(cards: &[&str]) -> HashSet<Card> {
+        cards.iter().map(|c| Card::from(c)).collect()
+    }
+
+    #[test]
+    fn best_hands_are_kept_best_first() {
+        let mut stats = PlayerStats::default();
+        let up_card = Card::from("5C");
+        stats.count_hand(&cards(&["2H", "4D", "6S", "8C"]), up_card, 0);
+        stats.count_hand(&cards(&["5H", "5D", "5S", "JC"]), up_card, 29);
+        for _ in 0..BEST_HANDS {
+            stats.count_hand(&cards(&["TH", "QD", "4S", "8C"]), up_card, 4);
+        }
+        assert_eq!(BEST_HANDS, stats.best_hands.len());
+        assert_eq!(29, stats.best_hands[0].score);
+        assert!(stats.best_hands[1..].iter().all(|b| b.score == 4));
+        assert_eq!(1, stats.hand_scores[29]);
+        assert_eq!(1, stats.hand_scores[0]);
+        assert_eq!((29 + 4 * BEST_HANDS) as f64 / 7.0, stats.average_hand());
+    }
+
+    #[test]
+    fn averages_of_nothing_are_zero() {
+        let stats = PlayerStats::default();
+        assert_eq!(0.0, stats.average_hand());
+        assert_eq!(0.0, stats.average_crib());
+        assert_eq!(0.0, stats.average_pegging());
+    }
+
+    #[test]
+    fn tallies_whole_games() {
+        let rules = GameRules::default();
+        let mut statistics = Statistics::new(rules);
+        for _ in 0..3 {
+            let (mut first, mut second) = (SimplePlayer {}, SimplePlayer {});
+            let mut runner = GameRunner::new(&mut first, &mut second, rules).unwrap();
+            loop {
+                let result = runner.step();
+                statistics.record(&result);
+                if let PlayResult::GameOver(_) = result {
+                    break;
+                }
+            }
+        }
+        let first = statistics.player(PlayerPosition::First);
+        let second = statistics.player(PlayerPosition::Second);
+        assert_eq!(3, first.games());
+        assert_eq!(first.games_won, second.games_lost);
+        assert_eq!(first.skunks_won, second.skunks_lost);
+        assert_eq!(first.deals, second.deals);
+        assert_eq!(first.hands, first.hand_scores.iter().sum::<u32>());
+        for stats in [first, second] {
+            assert!(stats.hands > 0 && stats.cribs > 0 && stats.pegging_points > 0);
+        }
+    }
+
+    #[test]
+    fn partners_win_together() {
+        let rules = GameRules::four_player_partners();
+        let mut players = [
+            SimplePlayer {},
+            SimplePlayer {},
+            SimplePlayer {},
+            SimplePlayer {},
+        ];
+        let seats = players
+            .iter_mut()
+            .map(|p| p as &mut dyn crate::player::KnowsCribbage)
+            .collect();
+        let mut runner = GameRunner::with_players(seats, rules).unwrap();
+        let mut statistics = Statistics::new(rules);
+        let winner = loop {
+            let result = runner.step();
+            statistics.record(&result);
+            if let PlayResult::GameOver(result) = result {
+                break result.winner;
+            }
+        };
+        let partner = rules.partner(winner).unwrap();
+        assert_eq!(1, statistics.player(winner).games_won);
+        assert_eq!(1, statistics.player(partner).games_won);
+        assert_eq!(1, statistics.player(winner.next(4)).games_lost);
+    }
+}
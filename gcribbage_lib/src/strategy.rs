@@ -0,0 +1,311 @@
+use crate::combinatorics::combinations;
+use crate::deck::{Card, Rank, Suit};
+use crate::player::{KnowsCribbage, SimplePlayer};
+use crate::scoring::{score_hand, score_pegging, HandScorings};
+use std::collections::HashSet;
+
+/// How well a computer player plays.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Difficulty {
+    /// Gives away its lowest cards and plays them first.
+    Easy,
+    /// Keeps the cards that score best and pegs whatever scores most.
+    Medium,
+    /// Weighs up the crib as well as its hand, and what its opponent could
+    /// score off each card it plays.
+    Hard,
+}
+
+impl Difficulty {
+    /// Makes an iterator for the difficulties, easiest first.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        use Difficulty::*;
+        [Easy, Medium, Hard].into_iter()
+    }
+
+    pub fn name(&self) -> &'static str {
+        use Difficulty::*;
+        match self {
+            Easy => "easy",
+            Medium => "medium",
+            Hard => "hard",
+        }
+    }
+
+    /// Finds the difficulty with the given name, in any case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::iter().find(|d| d.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Makes a computer player that plays this well.
+    pub fn player(&self) -> Box<dyn KnowsCribbage> {
+        match self {
+            Difficulty::Easy => Box::new(SimplePlayer {}),
+            Difficulty::Medium => Box::new(GreedyPlayer {}),
+            Difficulty::Hard => Box::new(ExpertPlayer {}),
+        }
+    }
+}
+
+/// One way of splitting a hand between the crib and what's kept, with what
+/// it's worth on average once the up card is cut.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscardOption {
+    pub discards: Vec<Card>,
+    pub kept: Vec<Card>,
+    /// The average score of the kept cards.
+    pub hand: f64,
+    /// The average the discards score in the crib, counting only what they
+    /// make with each other and the up card.
+    pub crib: f64,
+    /// What the choice is worth to the player: their hand, plus the crib
+    /// if it's theirs or less the crib if it isn't.
+    pub value: f64,
+}
+
+/// Works out every way of giving the given number of cards to the crib,
+/// best first.
+pub fn discard_options(
+    hand: &HashSet<Card>,
+    discards: usize,
+    own_crib: bool,
+) -> Vec<DiscardOption> {
+    let cuts = unseen(hand.iter());
+    let average = |score: &dyn Fn(Card) -> u8| {
+        cuts.iter().map(|cut| score(*cut) as f64).sum::<f64>() / cuts.len() as f64
+    };
+    let mut options = combinations(hand.iter(), discards)
+        .into_iter()
+        .map(|thrown| {
+            let kept = hand.difference(&thrown).copied().collect::<HashSet<Card>>();
+            let hand_value = average(&|cut| total(&score_hand(&kept, cut)));
+            /* The crib needs all five cards in a suit to score a flush, and
+             * there's no telling what the other cards in it will be. */
+            let crib_value = average(&|cut| {
+                let scorings = score_hand(&thrown, cut)
+                    .into_iter()
+                    .filter(|s| !is_flush(s))
+                    .collect::<Vec<HandScorings>>();
+                total(&scorings)
+            });
+            let value = match own_crib {
+                true => hand_value + crib_value,
+                false => hand_value - crib_value,
+            };
+            DiscardOption {
+                discards: sorted(thrown),
+                kept: sorted(kept),
+                hand: hand_value,
+                crib: crib_value,
+                value,
+            }
+        })
+        .collect::<Vec<DiscardOption>>();
+    options.sort_by(|a, b| {
+        b.value
+            .total_cmp(&a.value)
+            .then_with(|| a.discards.cmp(&b.discards))
+    });
+    options
+}
+
+/// One card that could be played, with what it pegs and what it's likely to
+/// give away.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayOption {
+    pub card: Card,
+    /// What playing the card pegs straight away.
+    pub points: u8,
+    /// What the next player pegs off it on average, if they can play.
+    pub risk: f64,
+}
+
+impl PlayOption {
+    pub fn value(&self) -> f64 {
+        self.points as f64 - self.risk
+    }
+}
+
+/// Works out what each card in the hand that can be played onto the count
+/// is worth, best first.
+pub fn play_options(hand: &HashSet<Card>, played: &[Card]) -> Vec<PlayOption> {
+    let count = count(played);
+    let replies = unseen(hand.iter().chain(played));
+    let mut options = hand
+        .iter()
+        .filter(|card| count + card.rank.value() <= 31)
+        .map(|card| {
+            let mut pile = played.to_vec();
+            pile.push(*card);
+            let points = pegged(&pile);
+            let pile_count = count + card.rank.value();
+            let risk = replies
+                .iter()
+                .filter(|reply| pile_count + reply.rank.value() <= 31)
+                .map(|reply| {
+                    let mut pile = pile.clone();
+                    pile.push(*reply);
+                    pegged(&pile) as f64
+                })
+                .sum::<f64>()
+                / replies.len() as f64;
+            PlayOption {
+                card: *card,
+                points,
+                risk,
+            }
+        })
+        .collect::<Vec<PlayOption>>();
+    /* Between cards worth the same, getting rid of the higher one keeps
+     * the low cards for when the count is nearly 31. */
+    options.sort_by(|a, b| {
+        b.value()
+            .total_cmp(&a.value())
+            .then_with(|| b.card.cmp(&a.card))
+    });
+    options
+}
+
+/// Keeps the cards that score best on their own and pegs whatever scores
+/// most straight away.
+pub struct GreedyPlayer {}
+
+impl KnowsCribbage for GreedyPlayer {
+    fn choose_crib(&mut self, hand: &HashSet<Card>, discards: usize, _own_crib: bool) -> Vec<Card> {
+        let mut options = discard_options(hand, discards, true);
+        options.sort_by(|a, b| b.hand.total_cmp(&a.hand));
+        options.remove(0).discards
+    }
+
+    fn play(&mut self, hand: &HashSet<Card>, played: &[Card]) -> Card {
+        let mut options = play_options(hand, played);
+        options.sort_by(|a, b| b.points.cmp(&a.points).then_with(|| b.card.cmp(&a.card)));
+        options[0].card
+    }
+}
+
+/// Plays by the analysis: the crib counts for or against each discard,
+/// and each card played is weighed against what it gives away.
+pub struct ExpertPlayer {}
+
+impl KnowsCribbage for ExpertPlayer {
+    fn choose_crib(&mut self, hand: &HashSet<Card>, discards: usize, own_crib: bool) -> Vec<Card> {
+        discard_options(hand, discards, own_crib).remove(0).discards
+    }
+
+    fn play(&mut self, hand: &HashSet<Card>, played: &[Card]) -> Card {
+        play_options(hand, played)[0].card
+    }
+}
+
+/// Every card that isn't one of the given ones.
+fn unseen<'a>(seen: impl Iterator<Item = &'a Card>) -> Vec<Card> {
+    let seen = seen.collect::<HashSet<&Card>>();
+    Suit::iter()
+        .flat_map(|suit| Rank::iter().map(move |rank| Card::new(suit, rank)))
+        .filter(|card| !seen.contains(card))
+        .collect()
+}
+
+fn sorted(cards: HashSet<Card>) -> Vec<Card> {
+    let mut cards = cards.into_iter().collect::<Vec<Card>>();
+    cards.sort();
+    cards
+}
+
+fn count(played: &[Card]) -> u8 {
+    played.iter().map(|c| c.rank.value()).sum()
+}
+
+fn pegged(played: &[Card]) -> u8 {
+    score_pegging(played.to_vec())
+        .iter()
+        .map(|s| s.value())
+        .sum()
+}
+
+fn total(scorings: &[HandScorings]) -> u8 {
+    scorings.iter().map(|s| s.value()).sum()
+}
+
+fn is_flush(scoring: &HandScorings) -> bool {
+    matches!(
+        scoring,
+        HandScorings::ThreeCardFlush(_)
+            | HandScorings::FourCardFlush(_)
+            | HandScorings::FiveCardFlush(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(cards: &[&str]) -> HashSet<Card> {
+        cards.iter().map(|c| Card::from(c)).collect()
+    }
+
+    #[test]
+    fn difficulties_are_found_by_name() {
+        for difficulty in Difficulty::iter() {
+            assert_eq!(Some(difficulty), Difficulty::from_name(difficulty.name()));
+        }
+        assert_eq!(Some(Difficulty::Hard), Difficulty::from_name("Hard"));
+        assert_eq!(None, Difficulty::from_name("impossible"));
+    }
+
+    #[test]
+    fn keeps_the_fives_and_tens() {
+        let hand = cards(&["5H", "5S", "TD", "KC", "2C", "9H"]);
+        let best = &discard_options(&hand, 2, false)[0];
+        assert_eq!(vec![Card::from("2C"), Card::from("9H")], best.discards);
+        assert!(best.hand > 10.0);
+    }
+
+    #[test]
+    fn the_crib_counts_for_the_dealer_and_against_the_pone() {
+        let hand = cards(&["5H", "5S", "AD", "2C", "3H", "4S"]);
+        let dealing = &discard_options(&hand, 2, true)[0];
+        let ponying = &discard_options(&hand, 2, false)[0];
+        assert!(dealing.value > ponying.value);
+        let pair = [Card::from("5H"), Card::from("5S")];
+        assert_ne!(pair.to_vec(), ponying.discards);
+    }
+
+    #[test]
+    fn every_discard_is_considered() {
+        let hand = cards(&["5H", "5S", "TD", "KC", "2C", "9H"]);
+        assert_eq!(15, discard_options(&hand, 2, true).len());
+    }
+
+    #[test]
+    fn takes_the_fifteen() {
+        let hand = cards(&["5H", "9C", "AS"]);
+        let played = vec![Card::from("KD")];
+        assert_eq!(Card::from("5H"), play_options(&hand, &played)[0].card);
+    }
+
+    #[test]
+    fn only_cards_that_can_be_played_are_considered() {
+        let hand = cards(&["9H", "AS"]);
+        let played = vec![Card::from("KD"), Card::from("QD"), Card::from("5C")];
+        let options = play_options(&hand, &played);
+        assert_eq!(1, options.len());
+        assert_eq!(Card::from("AS"), options[0].card);
+    }
+
+    #[test]
+    fn doesnt_lead_a_five() {
+        let hand = cards(&["5H", "4C", "KS", "2D"]);
+        assert_ne!(Card::from("5H"), ExpertPlayer {}.play(&hand, &[]));
+    }
+
+    #[test]
+    fn players_play_legal_cards() {
+        let hand = cards(&["9H", "AS", "KC"]);
+        let played = vec![Card::from("KD"), Card::from("QD"), Card::from("5C")];
+        for difficulty in Difficulty::iter() {
+            assert_eq!(Card::from("AS"), difficulty.player().play(&hand, &played));
+        }
+    }
+}
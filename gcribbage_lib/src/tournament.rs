@@ -0,0 +1,227 @@
+use crate::game::{GameResult, GameRunner, SeatingError};
+use crate::player::{KnowsCribbage, PlayerPosition};
+use crate::rules::GameRules;
+use crate::simulation::game_seed;
+
+/// The rating every player starts a tournament with.
+pub const STARTING_ELO: f64 = 1500.0;
+
+/// How far one game can move a player's rating.
+const ELO_K: f64 = 16.0;
+
+/// The chance of a player with the first rating beating one with the
+/// second, by the Elo system.
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// The ratings of a winner and loser after a game between them.
+pub fn elo_update(winner: f64, loser: f64) -> (f64, f64) {
+    let change = ELO_K * (1.0 - expected_score(winner, loser));
+    (winner + change, loser - change)
+}
+
+struct Entrant {
+    name: String,
+    player: Box<dyn KnowsCribbage>,
+}
+
+/// How a player did over a tournament.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    /// Games won with the loser short of the skunk line, counting double
+    /// skunks.
+    pub skunks: u32,
+    pub points_for: u32,
+    pub points_against: u32,
+    pub elo: f64,
+}
+
+impl Standing {
+    fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            games: 0,
+            wins: 0,
+            skunks: 0,
+            points_for: 0,
+            points_against: 0,
+            elo: STARTING_ELO,
+        }
+    }
+
+    pub fn losses(&self) -> u32 {
+        self.games - self.wins
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        match self.games {
+            0 => 0.0,
+            games => self.wins as f64 / games as f64,
+        }
+    }
+}
+
+/// A round robin between computer players, two at a time. Every pair plays
+/// the same deals twice, swapping seats the second time, so neither gets
+/// luckier cards than the other.
+pub struct Tournament {
+    entrants: Vec<Entrant>,
+    rules: GameRules,
+    /// How many deals each pair plays, each of them twice.
+    deals: u32,
+    seed: u64,
+}
+
+impl Tournament {
+    /// Sets up a tournament, as long as the rules are for two players.
+    pub fn new(rules: GameRules, deals: u32, seed: u64) -> Result<Self, SeatingError> {
+        if rules.players != 2 {
+            return Err(SeatingError::WrongNumberOfPlayers {
+                rules: rules.players,
+                seated: 2,
+            });
+        }
+        Ok(Self {
+            entrants: Vec::new(),
+            rules,
+            deals,
+            seed,
+        })
+    }
+
+    /// Enters a player in the tournament under the given name.
+    pub fn register(&mut self, name: &str, player: Box<dyn KnowsCribbage>) {
+        self.entrants.push(Entrant {
+            name: String::from(name),
+            player,
+        });
+    }
+
+    /// Plays a game with the first player given in the first seat, shuffled
+    /// from the given seed.
+    fn play_game(
+        first: &mut dyn KnowsCribbage,
+        second: &mut dyn KnowsCribbage,
+        rules: GameRules,
+        seed: u64,
+        dealer: PlayerPosition,
+    ) -> GameResult {
+        let mut runner = GameRunner::new(first, second, rules)
+            .expect("Tournament::new only takes rules for two players");
+        runner.set_seed(seed);
+        runner.set_first_dealer(dealer);
+        runner.play_to_end()
+    }
+
+    /// Plays every pair of players against each other, and gives back the
+    /// standings, best first. Ratings are updated game by game as they're
+    /// played.
+    pub fn play(&mut self) -> Vec<Standing> {
+        let mut standings = self
+            .entrants
+            .iter()
+            .map(|e| Standing::new(&e.name))
+            .collect::<Vec<Standing>>();
+        for j in 0..self.entrants.len() {
+            for i in 0..j {
+                for deal in 0..self.deals {
+                    let seed = game_seed(self.seed, deal);
+                    let dealer = match deal % 2 {
+                        0 => PlayerPosition::First,
+                        _ => PlayerPosition::Second,
+                    };
+                    for (a, b) in [(i, j), (j, i)] {
+                        let (left, right) = self.entrants.split_at_mut(j);
+                        let (at_i, at_j) = (left[i].player.as_mut(), right[0].player.as_mut());
+                        let result = match a == i {
+                            true => Self::play_game(at_i, at_j, self.rules, seed, dealer),
+                            false => Self::play_game(at_j, at_i, self.rules, seed, dealer),
+                        };
+                        record(&mut standings, [a, b], &result);
+                    }
+                }
+            }
+        }
+        standings.sort_by(|a, b| b.wins.cmp(&a.wins).then(b.elo.total_cmp(&a.elo)));
+        standings
+    }
+}
+
+/// Adds a game to the standings of the players in the given seats.
+fn record(standings: &mut [Standing], seats: [usize; 2], result: &GameResult) {
+    let (winner, loser) = match result.winner {
+        PlayerPosition::First => (seats[0], seats[1]),
+        _ => (seats[1], seats[0]),
+    };
+    let score = |p| result.scores.get(&p).copied().unwrap_or(0) as u32;
+    let winner_score = score(result.winner);
+    let loser_score = score(result.winner.next(2));
+    let (winner_elo, loser_elo) = elo_update(standings[winner].elo, standings[loser].elo);
+
+    let standing = &mut standings[winner];
+    standing.games += 1;
+    standing.wins += 1;
+    standing.skunks += result.skunk.is_some() as u32;
+    standing.points_for += winner_score;
+    standing.points_against += loser_score;
+    standing.elo = winner_elo;
+
+    let standing = &mut standings[loser];
+    standing.games += 1;
+    standing.points_for += loser_score;
+    standing.points_against += winner_score;
+    standing.elo = loser_elo;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::SimplePlayer;
+
+    #[test]
+    fn upsets_move_ratings_further() {
+        assert_eq!(0.5, expected_score(1500.0, 1500.0));
+        assert!(expected_score(1700.0, 1500.0) > 0.75);
+
+        let (winner, loser) = elo_update(1500.0, 1500.0);
+        assert_eq!((1508.0, 1492.0), (winner, loser));
+        let (favourite, _) = elo_update(1700.0, 1500.0);
+        let (underdog, _) = elo_update(1500.0, 1700.0);
+        assert!(underdog - 1500.0 > favourite - 1700.0);
+    }
+
+    #[test]
+    fn every_pair_plays_both_seats() {
+        let mut tournament = Tournament::new(GameRules::new(), 2, 5).unwrap();
+        tournament.register("first", Box::new(SimplePlayer {}));
+        tournament.register("second", Box::new(SimplePlayer {}));
+        tournament.register("third", Box::new(SimplePlayer {}));
+        let standings = tournament.play();
+
+        assert_eq!(3, standings.len());
+        for standing in &standings {
+            /* Two opponents, two deals each, played from both seats. */
+            assert_eq!(8, standing.games);
+        }
+        let wins = standings.iter().map(|s| s.wins).sum::<u32>();
+        assert_eq!(12, wins);
+        let elo = standings.iter().map(|s| s.elo).sum::<f64>();
+        assert!((elo - 3.0 * STARTING_ELO).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tournaments_are_for_two_players() {
+        let tournament = Tournament::new(GameRules::three_player(), 2, 5);
+        assert_eq!(
+            Some(SeatingError::WrongNumberOfPlayers {
+                rules: 3,
+                seated: 2
+            }),
+            tournament.err()
+        );
+    }
+}
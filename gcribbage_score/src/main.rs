@@ -0,0 +1,166 @@
+use gcribbage_lib::deck::Card;
+use gcribbage_lib::scoring::{score_crib, score_hand, HandScorings};
+use gcribbage_lib::strategy;
+use std::collections::HashSet;
+
+const USAGE: &str = "Usage: gcribbage_score [--crib] CARD...
+
+Scores a cribbage hand. Give the four cards in the hand and then the up card,
+such as \"5h 5d 5s jc 5c\", and every way it scores is listed with the total.
+With --crib, it's counted as a crib, where only a five-card flush scores.
+
+Give six cards instead to see every way of discarding two of them, best
+first, by what they're worth on average. With --crib, the crib is yours.";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
+
+/// Reads the cards typed, which can be split by spaces or commas, such as
+/// "5h 5d" or "5h,10d".
+fn parse_cards(words: &[String]) -> Result<Vec<Card>, String> {
+    let mut cards = Vec::new();
+    for word in words.iter().flat_map(|w| w.split(',')) {
+        if word.is_empty() {
+            continue;
+        }
+        let card = Card::parse(word).ok_or_else(|| format!("{:?} isn't a card.", word))?;
+        if cards.contains(&card) {
+            return Err(format!("{} is there twice.", card.short_name()));
+        }
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+fn card_names(cards: &[Card]) -> String {
+    let names = cards.iter().map(Card::short_name).collect::<Vec<String>>();
+    names.join(" ")
+}
+
+/// Lists everything the hand scores with the up card, and the total.
+fn show_score(hand: &[Card], up_card: Card, crib: bool) {
+    let held = hand.iter().copied().collect::<HashSet<Card>>();
+    let mut scorings = match crib {
+        true => score_crib(&held, up_card),
+        false => score_hand(&held, up_card),
+    };
+    scorings.sort_by_key(HandScorings::counting_order);
+    let title = match crib {
+        true => "Crib",
+        false => "Hand",
+    };
+    let mut hand = hand.to_vec();
+    hand.sort();
+    println!(
+        "{} {}, with {} up",
+        title,
+        card_names(&hand),
+        up_card.short_name()
+    );
+    for scoring in &scorings {
+        println!(
+            "  {:<10}{:<18}{}",
+            scoring.name(),
+            card_names(&scoring.cards()),
+            scoring.value()
+        );
+    }
+    let total: u8 = scorings.iter().map(HandScorings::value).sum();
+    println!("  {:<28}{}", "Total", total);
+}
+
+/// Lists every way of discarding two cards from the hand, best first.
+fn show_discards(hand: &[Card], own_crib: bool) {
+    let held = hand.iter().copied().collect::<HashSet<Card>>();
+    let whose = match own_crib {
+        true => "your",
+        false => "your opponent's",
+    };
+    let mut hand = hand.to_vec();
+    hand.sort();
+    println!("Discarding from {} to {} crib", card_names(&hand), whose);
+    println!();
+    println!(
+        "{:<4}{:<10}{:<14}{:>7}{:>7}{:>8}",
+        "", "Discard", "Keep", "Hand", "Crib", "Value"
+    );
+    for (place, option) in strategy::discard_options(&held, 2, own_crib)
+        .iter()
+        .enumerate()
+    {
+        println!(
+            "{:<4}{:<10}{:<14}{:>7.2}{:>7.2}{:>+8.2}",
+            place + 1,
+            card_names(&option.discards),
+            card_names(&option.kept),
+            option.hand,
+            option.crib,
+            option.value
+        );
+    }
+    println!();
+    println!("Hand and crib are what the cards kept and thrown score on average");
+    println!("over every up card. Value adds the crib to the hand if it's yours,");
+    println!("or takes it away if it isn't.");
+}
+
+fn main() {
+    let mut crib = false;
+    let mut words = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--crib" => crib = true,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if arg.starts_with("--") => usage(),
+            _ => words.push(arg),
+        }
+    }
+    let cards = match parse_cards(&words) {
+        Ok(cards) => cards,
+        Err(why) => {
+            eprintln!("{}", why);
+            std::process::exit(2);
+        }
+    };
+    match cards.len() {
+        5 => show_score(&cards[..4], cards[4], crib),
+        6 => show_discards(&cards, crib),
+        _ => usage(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        text.split(' ').map(String::from).collect()
+    }
+
+    #[test]
+    fn cards_are_read_as_typed() {
+        let expected = ["5H", "5D", "5S", "JC", "5C"].map(Card::from).to_vec();
+        assert_eq!(Ok(expected.clone()), parse_cards(&words("5h 5d 5s jc 5c")));
+        assert_eq!(Ok(expected), parse_cards(&words("5h,5d, 5s jc,5c")));
+        assert_eq!(Ok(vec![Card::from("TD")]), parse_cards(&words("10d")));
+    }
+
+    #[test]
+    fn jack_turned_up_is_not_his_nob() {
+        let cards = parse_cards(&words("5h 5d 5s 5c jc")).unwrap();
+        let scorings = score_hand(&HashSet::from_iter(cards[..4].to_vec()), cards[4]);
+        assert!(scorings.iter().all(|s| s.name() != "His nob"));
+        assert_eq!(28, scorings.iter().map(HandScorings::value).sum::<u8>());
+    }
+
+    #[test]
+    fn bad_cards_are_explained() {
+        assert!(parse_cards(&words("5h 5x")).is_err());
+        assert!(parse_cards(&words("5h 5H")).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use gcribbage_lib::player::PlayerPosition;
+use gcribbage_lib::rules::GameRules;
+use gcribbage_lib::simulation::Simulation;
+use gcribbage_lib::strategy::Difficulty;
+use gcribbage_lib::tournament::Tournament;
+use std::time::Instant;
+
+const USAGE: &str = "Usage: gcribbage_sim [--games N] [--seed S] [--short] FIRST SECOND
+       gcribbage_sim --tournament [--games N] [--seed S] [--short] [PLAYER...]
+
+Plays computer players against each other and reports how each did. The
+players are named by how well they play: easy, medium or hard. They take
+turns dealing first, and the same seed deals the same cards.
+
+A tournament is a round robin between the players given, or all of them if
+none are. Each pair plays N deals twice, swapping seats for the second, and
+the players are ranked by wins and rated by Elo.";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
+
+/// What was asked for on the command line.
+struct Options {
+    players: Vec<Difficulty>,
+    tournament: bool,
+    games: u32,
+    seed: u64,
+    rules: GameRules,
+}
+
+fn parse_args() -> Options {
+    let mut options = Options {
+        players: Vec::new(),
+        tournament: false,
+        games: 1000,
+        seed: 0,
+        rules: GameRules::new(),
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" | "-n" => {
+                let games = args.next().and_then(|n| n.parse().ok());
+                options.games = games.unwrap_or_else(|| usage());
+            }
+            "--seed" => {
+                let seed = args.next().and_then(|n| n.parse().ok());
+                options.seed = seed.unwrap_or_else(|| usage());
+            }
+            "--short" => options.rules = GameRules::short_game(),
+            "--tournament" => options.tournament = true,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            name => match Difficulty::from_name(name) {
+                Some(difficulty) => options.players.push(difficulty),
+                None => usage(),
+            },
+        }
+    }
+    if options.tournament && options.players.is_empty() {
+        options.players = Difficulty::iter().collect();
+    }
+    let players = options.players.len();
+    match options.tournament {
+        true if players < 2 => usage(),
+        false if players != 2 => usage(),
+        _ if options.games == 0 => usage(),
+        _ => (),
+    }
+    options
+}
+
+fn percent(rate: f64) -> String {
+    format!("{:.1}%", rate * 100.0)
+}
+
+fn run_tournament(options: &Options) {
+    let mut tournament = Tournament::new(options.rules, options.games, options.seed)
+        .unwrap_or_else(|why| {
+            eprintln!("{}", why);
+            std::process::exit(2);
+        });
+    for difficulty in &options.players {
+        tournament.register(difficulty.name(), difficulty.player());
+    }
+    let started = Instant::now();
+    let standings = tournament.play();
+    println!(
+        "Round robin of {} deals a pair, each played from both seats, to {}, seed {}, in {:.1}s",
+        options.games,
+        options.rules.target_score,
+        options.seed,
+        started.elapsed().as_secs_f64()
+    );
+    println!();
+    println!(
+        "{:<6}{:<8}{:>7}{:>7}{:>7}{:>8}{:>8}{:>8}{:>7}",
+        "", "Player", "Games", "Won", "Lost", "Win %", "Skunks", "Spread", "Elo"
+    );
+    for (place, standing) in standings.iter().enumerate() {
+        let spread = standing.points_for as i64 - standing.points_against as i64;
+        println!(
+            "{:<6}{:<8}{:>7}{:>7}{:>7}{:>8}{:>8}{:>+8}{:>7.0}",
+            place + 1,
+            standing.name,
+            standing.games,
+            standing.wins,
+            standing.losses(),
+            percent(standing.win_rate()),
+            standing.skunks,
+            spread,
+            standing.elo,
+        );
+    }
+    println!();
+    println!("Spread is the points scored less the points scored against.");
+}
+
+fn main() {
+    let options = parse_args();
+    if options.tournament {
+        run_tournament(&options);
+        return;
+    }
+    let mut first = options.players[0].player();
+    let mut second = options.players[1].player();
+    let started = Instant::now();
+    let simulation = Simulation::run(
+        first.as_mut(),
+        second.as_mut(),
+        options.rules,
+        options.games,
+        options.seed,
+    )
+    .unwrap_or_else(|why| {
+        eprintln!("{}", why);
+        std::process::exit(2);
+    });
+    println!(
+        "{} games to {}, seed {}, in {:.1}s",
+        options.games,
+        options.rules.target_score,
+        options.seed,
+        started.elapsed().as_secs_f64()
+    );
+    println!();
+    println!(
+        "{:<8}{:>8}{:>18}{:>9}{:>8}{:>9}{:>7}{:>7}",
+        "Player", "Wins", "95% interval", "Margin", "Skunks", "Pegging", "Hand", "Crib"
+    );
+    for (position, difficulty) in PlayerPosition::iter(2).zip(&options.players) {
+        let stats = simulation.statistics.player(position);
+        let games = stats.games().max(1) as f64;
+        let (low, high) = simulation.win_interval(position);
+        println!(
+            "{:<8}{:>8}{:>18}{:>+9.1}{:>8}{:>9.1}{:>7.1}{:>7.1}",
+            difficulty.name(),
+            percent(simulation.win_rate(position)),
+            format!("{} – {}", percent(low), percent(high)),
+            simulation.average_margin(position),
+            percent(simulation.skunk_rate(position)),
+            stats.pegging_points as f64 / games,
+            stats.hand_points as f64 / games,
+            stats.crib_points as f64 / games,
+        );
+    }
+    println!();
+    println!("Margin is the average points ahead at the end of a game. Pegging,");
+    println!("hand and crib are the average points a game scored each way.");
+}
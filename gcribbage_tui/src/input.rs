@@ -0,0 +1,137 @@
+use crate::render::hand_with_indices;
+use gcribbage_lib::deck::Card;
+use gcribbage_lib::player::KnowsCribbage;
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+
+/// Works out which cards from the hand the person typed, either by where
+/// they are in the hand, counting from 1, or written out like "5h". Exactly
+/// the given number have to be picked, and none of them twice.
+pub fn parse_choice(text: &str, hand: &[Card], count: usize) -> Result<Vec<Card>, String> {
+    let mut chosen = Vec::new();
+    for word in text.split(|c: char| c.is_whitespace() || c == ',') {
+        if word.is_empty() {
+            continue;
+        }
+        let card = match word.parse::<usize>() {
+            Ok(i) if (1..=hand.len()).contains(&i) => hand[i - 1],
+            Ok(i) => return Err(format!("There's no card {} in your hand.", i)),
+            Err(_) => match Card::parse(word) {
+                Some(card) if hand.contains(&card) => card,
+                Some(card) => return Err(format!("{} isn't in your hand.", card.short_name())),
+                None => return Err(format!("{:?} isn't a card.", word)),
+            },
+        };
+        if chosen.contains(&card) {
+            return Err(format!("{} was picked twice.", card.short_name()));
+        }
+        chosen.push(card);
+    }
+    match chosen.len() == count {
+        true => Ok(chosen),
+        false if count == 1 => Err(String::from("Pick one card.")),
+        false => Err(format!("Pick {} cards.", count)),
+    }
+}
+
+/// Asks the person a question, and gives back the line they answer with.
+/// There's no game without them, so it ends when they stop typing.
+fn ask(question: &str) -> String {
+    print!("{}", question);
+    io::stdout()
+        .flush()
+        .expect("Could not write to the terminal");
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => {
+            println!();
+            std::process::exit(0);
+        }
+        Ok(_) => line,
+    }
+}
+
+/// The person at the terminal, who is asked for their choices as the game
+/// needs them.
+pub struct TerminalPlayer {}
+
+impl KnowsCribbage for TerminalPlayer {
+    fn choose_crib(&mut self, hand: &HashSet<Card>, discards: usize, own_crib: bool) -> Vec<Card> {
+        let hand = sorted(hand);
+        let whose = match own_crib {
+            true => "your",
+            false => "the computer's",
+        };
+        println!("Your hand: {}", hand_with_indices(&hand));
+        loop {
+            let question = format!("Choose {} cards for {} crib: ", discards, whose);
+            match parse_choice(&ask(&question), &hand, discards) {
+                Ok(cards) => return cards,
+                Err(why) => println!("{}", why),
+            }
+        }
+    }
+
+    fn play(&mut self, hand: &HashSet<Card>, played: &[Card]) -> Card {
+        let hand = sorted(hand);
+        let count: u8 = played.iter().map(|c| c.rank.value()).sum();
+        println!("Your hand: {}", hand_with_indices(&hand));
+        loop {
+            let card = match parse_choice(&ask("Play a card: "), &hand, 1) {
+                Ok(cards) => cards[0],
+                Err(why) => {
+                    println!("{}", why);
+                    continue;
+                }
+            };
+            match count + card.rank.value() <= 31 {
+                true => return card,
+                false => println!("{} would take the count over 31.", card.short_name()),
+            }
+        }
+    }
+}
+
+fn sorted(cards: &HashSet<Card>) -> Vec<Card> {
+    let mut cards = cards.iter().copied().collect::<Vec<Card>>();
+    cards.sort();
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand() -> Vec<Card> {
+        ["AS", "5H", "5D", "JC", "KH", "QD"]
+            .iter()
+            .map(|c| Card::from(c))
+            .collect()
+    }
+
+    #[test]
+    fn cards_are_picked_by_place_or_name() {
+        let tests = vec![
+            ("1 3", vec!["AS", "5D"]),
+            ("5h, kh", vec!["5H", "KH"]),
+            ("  6 jc ", vec!["QD", "JC"]),
+        ];
+
+        for (text, expected) in tests {
+            let expected = expected
+                .iter()
+                .map(|c| Card::from(c))
+                .collect::<Vec<Card>>();
+            assert_eq!(Ok(expected), parse_choice(text, &hand(), 2), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn bad_choices_are_explained() {
+        let tests = vec!["1", "1 2 3", "7 1", "0 1", "2c 1", "xx 1", "1 as"];
+
+        for text in tests {
+            assert!(parse_choice(text, &hand(), 2).is_err(), "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+mod input;
+mod render;
+use gcribbage_lib::game::{GameRunner, PlayResult};
+use gcribbage_lib::player::PlayerPosition;
+use gcribbage_lib::rules::GameRules;
+use gcribbage_lib::strategy::Difficulty;
+use input::TerminalPlayer;
+use render::Display;
+
+/// The seat the person sits in. The computer sits in the other one.
+const HUMAN: PlayerPosition = PlayerPosition::First;
+const COMPUTER: PlayerPosition = PlayerPosition::Second;
+
+const USAGE: &str = "Usage: gcribbage_tui [--difficulty easy|medium|hard] [--short]
+
+Play a game of cribbage against the computer. Cards are picked by where they
+are in your hand, counting from 1, or written out like \"5h\" or \"10d\".";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
+
+fn main() {
+    let mut difficulty = Difficulty::Hard;
+    let mut rules = GameRules::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--difficulty" => {
+                let name = args.next().unwrap_or_else(|| usage());
+                difficulty = Difficulty::from_name(&name).unwrap_or_else(|| usage());
+            }
+            "--short" => rules = GameRules::short_game(),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => usage(),
+        }
+    }
+
+    let mut human = TerminalPlayer {};
+    let mut computer = difficulty.player();
+    let mut runner = GameRunner::new(&mut human, computer.as_mut(), rules)
+        .expect("The terminal game is for two players");
+    let mut display = Display::default();
+    println!(
+        "Playing to {} against the {} computer.",
+        rules.target_score,
+        difficulty.name()
+    );
+    loop {
+        let result = runner.step();
+        display.show(&result);
+        if matches!(result, PlayResult::GameOver(_) | PlayResult::Incomplete) {
+            break;
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{COMPUTER, HUMAN};
+use gcribbage_lib::deck::Card;
+use gcribbage_lib::game::PlayResult;
+use gcribbage_lib::player::PlayerPosition;
+use gcribbage_lib::scoring::{HandScorings, PeggingScorings};
+use std::collections::{HashMap, HashSet};
+
+pub fn card_names(cards: &[Card]) -> String {
+    let names = cards.iter().map(Card::short_name).collect::<Vec<String>>();
+    names.join(" ")
+}
+
+/// The cards with the number each can be picked by, such as "1) A♠  2) 5♥".
+pub fn hand_with_indices(cards: &[Card]) -> String {
+    let names = cards
+        .iter()
+        .enumerate()
+        .map(|(i, card)| format!("{}) {}", i + 1, card.short_name()))
+        .collect::<Vec<String>>();
+    names.join("  ")
+}
+
+fn who(position: PlayerPosition) -> &'static str {
+    match position == HUMAN {
+        true => "You",
+        false => "The computer",
+    }
+}
+
+/// Who did something, such as "You play" or "The computer plays".
+fn who_does(position: PlayerPosition, verb: &str) -> String {
+    match position == HUMAN {
+        true => format!("You {}", verb),
+        false => format!("The computer {}s", verb),
+    }
+}
+
+/// What's called out for what a card pegged, such as "fifteen for 2".
+fn pegging_calls(scorings: &[PeggingScorings]) -> Vec<String> {
+    scorings
+        .iter()
+        .map(|scoring| {
+            let name = match scoring {
+                PeggingScorings::Fifteen => "fifteen",
+                PeggingScorings::Pair(_) => "pair",
+                PeggingScorings::RunOfThree => "run of 3",
+                PeggingScorings::RunOfFour => "run of 4",
+                PeggingScorings::RunOfFive => "run of 5",
+                PeggingScorings::RunOfSix => "run of 6",
+                PeggingScorings::RunOfSeven => "run of 7",
+                PeggingScorings::ThirtyOne => "31",
+                PeggingScorings::LastCard => "last card",
+            };
+            format!("{} for {}", name, scoring.value())
+        })
+        .collect()
+}
+
+/// Prints what happens in a game as it's played, keeping track of what's
+/// on the table to work out who did what.
+#[derive(Default)]
+pub struct Display {
+    hands: HashMap<PlayerPosition, HashSet<Card>>,
+    crib: HashSet<Card>,
+    pile: Vec<Card>,
+    up_card: Option<Card>,
+    scores: HashMap<PlayerPosition, u8>,
+}
+
+impl Display {
+    fn score(&self, position: PlayerPosition) -> u8 {
+        self.scores.get(&position).copied().unwrap_or(0)
+    }
+
+    fn scoreboard(&self) -> String {
+        format!(
+            "Score: you {}, the computer {}",
+            self.score(HUMAN),
+            self.score(COMPUTER)
+        )
+    }
+
+    /// Whoever's hand the given card was in.
+    fn holder(&self, card: Card) -> Option<PlayerPosition> {
+        self.hands
+            .iter()
+            .find(|(_, hand)| hand.contains(&card))
+            .map(|(position, _)| *position)
+    }
+
+    pub fn show(&mut self, result: &PlayResult) {
+        match result {
+            PlayResult::DealerChosen(info) => {
+                println!(
+                    "You cut {}, the computer cuts {}. {} first.",
+                    info.chosen_cards[&HUMAN].short_name(),
+                    info.chosen_cards[&COMPUTER].short_name(),
+                    who_does(info.dealer, "deal"),
+                );
+            }
+            PlayResult::CardsDealt(info) => {
+                self.scores = info.scores.clone();
+                self.hands = info.hands.clone();
+                self.pile.clear();
+                self.up_card = None;
+                println!();
+                println!("{}. {}.", who_does(info.dealer, "deal"), self.scoreboard());
+            }
+            PlayResult::CribChosen(info) => {
+                self.crib = info.crib.clone();
+                self.hands = info.hands.clone();
+            }
+            PlayResult::WaitingForPlay(info) => {
+                if self.up_card.is_none() {
+                    self.up_card = Some(info.up_card);
+                    println!("The up card is {}.", info.up_card.short_name());
+                    let heels = info.scores[&info.dealer].saturating_sub(self.score(info.dealer));
+                    if heels > 0 {
+                        println!("{} {} for heels.", who_does(info.dealer, "take"), heels);
+                    }
+                }
+                if let (true, Some(card)) =
+                    (info.played.len() > self.pile.len(), info.played.last())
+                {
+                    let position = self.holder(*card).unwrap_or(info.current_player);
+                    let count: u8 = info.played.iter().map(|c| c.rank.value()).sum();
+                    let mut line = format!(
+                        "{} {}. Count {}",
+                        who_does(position, "play"),
+                        card.short_name(),
+                        count
+                    );
+                    for call in pegging_calls(&info.scorings) {
+                        line = format!("{}, {}", line, call);
+                    }
+                    println!("{}.", line);
+                }
+                self.pile = info.played.clone();
+                self.hands = info.hands.clone();
+                self.scores = info.scores.clone();
+                if info.current_player == HUMAN && self.pile.is_empty() {
+                    println!("Your lead. {}.", self.scoreboard());
+                } else if info.current_player == HUMAN {
+                    let count: u8 = self.pile.iter().map(|c| c.rank.value()).sum();
+                    println!(
+                        "Pile: {}, count {}. {}.",
+                        card_names(&self.pile),
+                        count,
+                        self.scoreboard()
+                    );
+                }
+            }
+            PlayResult::Go(info) => {
+                println!("{}: Go.", who(info.caller));
+                self.pile = info.played.clone();
+                self.hands = info.hands.clone();
+                self.scores = info.scores.clone();
+            }
+            PlayResult::WaitingForLastCardAcknowledgement(info) => {
+                let pegged = info
+                    .scores
+                    .iter()
+                    .find(|(position, score)| **score > self.score(**position));
+                if let Some((position, _)) = pegged {
+                    for call in pegging_calls(&info.scorings) {
+                        let (first, rest) = call.split_at(1);
+                        println!("{}: {}{}.", who(*position), first.to_uppercase(), rest);
+                    }
+                }
+                println!("The count starts again.");
+                self.pile.clear();
+                self.hands = info.hands.clone();
+                self.scores = info.scores.clone();
+            }
+            PlayResult::HandScore(info) => {
+                let mut hand = info.hand.iter().copied().collect::<Vec<Card>>();
+                hand.sort();
+                let whose = match (info.hand == self.crib, info.player == HUMAN) {
+                    (true, true) => "Your crib",
+                    (true, false) => "The computer's crib",
+                    (false, true) => "Your hand",
+                    (false, false) => "The computer's hand",
+                };
+                println!();
+                println!(
+                    "{}: {}, with {} up",
+                    whose,
+                    card_names(&hand),
+                    info.up_card.short_name()
+                );
+                let mut scorings = info.scorings.clone().unwrap_or_default();
+                scorings.sort_by_key(HandScorings::counting_order);
+                for scoring in &scorings {
+                    println!(
+                        "  {:<10}{:<18}{}",
+                        scoring.name(),
+                        card_names(&scoring.cards()),
+                        scoring.value()
+                    );
+                }
+                let total: u8 = scorings.iter().map(HandScorings::value).sum();
+                println!("  {:<28}{}", "Total", total);
+                self.scores = info.scores.clone();
+            }
+            PlayResult::GameOver(result) => {
+                self.scores = result.scores.clone();
+                println!();
+                println!(
+                    "{} the game. {}.",
+                    who_does(result.winner, "win"),
+                    self.scoreboard()
+                );
+                if result.skunk.is_some() {
+                    println!("That's a skunk!");
+                }
+            }
+            PlayResult::Incomplete => (),
+        }
+    }
+}